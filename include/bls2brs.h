@@ -0,0 +1,35 @@
+#ifndef BLS2BRS_H
+#define BLS2BRS_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define BLS2BRS_OK 0
+#define BLS2BRS_INVALID_ARGUMENT 1
+#define BLS2BRS_READ_FAILED 2
+#define BLS2BRS_CONVERT_FAILED 3
+#define BLS2BRS_WRITE_FAILED 4
+#define BLS2BRS_INTERNAL_ERROR 5
+
+typedef struct Bls2brsReport {
+    size_t count_success;
+    size_t count_failure;
+    size_t count_unknown_ui_names;
+    size_t count_out_of_range;
+    size_t count_duplicates;
+    size_t brick_count;
+} Bls2brsReport;
+
+/* Converts the bls file at in_path to a brs file at out_path. Paths are UTF-8.
+ * Returns BLS2BRS_OK on success. out may be NULL. out_path is left alone unless the
+ * conversion succeeds. */
+int bls2brs_convert(const char *in_path, const char *out_path, Bls2brsReport *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,76 @@
+//! Compressed saves: gzipped bls files, and zip archives of them.
+
+use flate2::bufread::GzDecoder;
+use std::io::{self, prelude::*, BufReader};
+use zip::ZipArchive;
+
+/// Largest file read from a zip archive, in bytes, so a crafted archive can't take all
+/// memory.
+pub const MAX_ZIP_ENTRY: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zip,
+}
+
+/// Detects how a file is compressed from its first bytes, without consuming them.
+pub fn detect(r: &mut impl BufRead) -> io::Result<Compression> {
+    let header = r.fill_buf()?;
+
+    Ok(if header.starts_with(&[0x1f, 0x8b]) {
+        Compression::Gzip
+    } else if header.starts_with(b"PK\x03\x04") {
+        Compression::Zip
+    } else {
+        Compression::None
+    })
+}
+
+/// Reads the decompressed contents of a gzipped file.
+pub fn gunzip(r: impl BufRead) -> impl BufRead {
+    BufReader::new(GzDecoder::new(r))
+}
+
+/// Names of the `.bls` files in a zip archive, in archive order. Names that would point
+/// outside of a directory they're extracted to are left out.
+pub fn zip_saves(r: impl Read + Seek) -> io::Result<Vec<String>> {
+    let mut archive = ZipArchive::new(r).map_err(zip_error)?;
+    let mut names = Vec::new();
+
+    for index in 0..archive.len() {
+        let file = archive.by_index(index).map_err(zip_error)?;
+        if file.is_file()
+            && file.enclosed_name().is_some()
+            && file.name().to_lowercase().ends_with(".bls")
+        {
+            names.push(file.name().to_string());
+        }
+    }
+
+    Ok(names)
+}
+
+/// Reads one file of a zip archive. Fails if it is larger than `MAX_ZIP_ENTRY`.
+pub fn read_zip_entry(r: impl Read + Seek, name: &str) -> io::Result<Vec<u8>> {
+    let mut archive = ZipArchive::new(r).map_err(zip_error)?;
+    let file = archive.by_name(name).map_err(zip_error)?;
+    // Not sized from the archive, which can claim anything
+    let mut contents = Vec::new();
+    file.take(MAX_ZIP_ENTRY + 1).read_to_end(&mut contents)?;
+    if contents.len() as u64 > MAX_ZIP_ENTRY {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is larger than {} bytes", name, MAX_ZIP_ENTRY),
+        ));
+    }
+    Ok(contents)
+}
+
+fn zip_error(e: zip::result::ZipError) -> io::Error {
+    match e {
+        zip::result::ZipError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
@@ -0,0 +1,90 @@
+//! Brickadia brick asset names, to catch misspelled assets in mappings and
+//! `ConvertOptions::print_assets`. Brickadia loads bricks of unknown assets as invisible
+//! bricks without saying why.
+
+/// Brick assets of the latest Brickadia version, the default `ConvertOptions::known_assets`.
+pub const KNOWN_ASSETS: &[&str] = &[
+    // Procedural
+    "PB_DefaultArch",
+    "PB_DefaultBrick",
+    "PB_DefaultMicroBrick",
+    "PB_DefaultMicroWedge",
+    "PB_DefaultMicroWedgeCorner",
+    "PB_DefaultMicroWedgeHalfInnerCorner",
+    "PB_DefaultMicroWedgeHalfInnerCornerInverted",
+    "PB_DefaultMicroWedgeHalfOuterCorner",
+    "PB_DefaultMicroWedgeInnerCorner",
+    "PB_DefaultMicroWedgeOuterCorner",
+    "PB_DefaultMicroWedgeTriangleCorner",
+    "PB_DefaultPole",
+    "PB_DefaultRamp",
+    "PB_DefaultRampCorner",
+    "PB_DefaultRampCornerInverted",
+    "PB_DefaultRampCrest",
+    "PB_DefaultRampCrestCorner",
+    "PB_DefaultRampCrestEnd",
+    "PB_DefaultRampInnerCorner",
+    "PB_DefaultRampInnerCornerInverted",
+    "PB_DefaultRampInverted",
+    "PB_DefaultSideWedge",
+    "PB_DefaultSideWedgeTile",
+    "PB_DefaultSmoothTile",
+    "PB_DefaultStudded",
+    "PB_DefaultTile",
+    "PB_DefaultWedge",
+    // Fixed size
+    "B_1x1F_Octo",
+    "B_1x1F_Round",
+    "B_1x1_Brick_Side",
+    "B_1x1_Brick_Side_Lip",
+    "B_1x1_Cone",
+    "B_1x1_Round",
+    "B_1x2f_Plate_Center",
+    "B_1x2f_Plate_Center_Inv",
+    "B_1x4_Brick_Side",
+    "B_1x_Octo",
+    "B_2x2F_Octo",
+    "B_2x2F_Round",
+    "B_2x2_Cone",
+    "B_2x2_Corner",
+    "B_2x2_Round",
+    "B_2x_Octo",
+    "B_2x_Octo_90Deg",
+    "B_2x_Octo_Cone",
+    "B_2x_Octo_T",
+    "B_4x4_Round",
+    "B_8x8_Lattice_Plate",
+    "B_Bone",
+    "B_Bush",
+    "B_Coffin",
+    "B_Coffin_Lid",
+    "B_Fern",
+    "B_Flower",
+    "B_Gravestone",
+    "B_Handle",
+    "B_Hedge_1x1",
+    "B_Hedge_1x1_Corner",
+    "B_Hedge_1x2",
+    "B_Hedge_1x4",
+    "B_Inverted_Cone",
+    "B_Ladder",
+    "B_Picket_Fence",
+    "B_Pine_Tree",
+    "B_Pumpkin",
+    "B_Pumpkin_Carved",
+    "B_Small_Flower",
+    "B_Swirl_Plate",
+    "B_Turkey_Body",
+    "B_Turkey_Leg",
+];
+
+/// Reads a list of asset names, one per line. Blank lines and lines starting with `#` are
+/// left out.
+pub fn parse_list(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,507 @@
+//! A window for converting saves without a terminal. Saves are dropped onto it, each with
+//! its own options, starting from the ones in `bls2brs.toml`.
+//!
+//! Built with `cargo build --release --features gui`.
+
+#![windows_subsystem = "windows"]
+
+use bls2brs::{
+    archive::{self, Compression},
+    bl_save, brs,
+    config::Config,
+    convert_with_options, format,
+    owners::OwnerMap,
+    ColorSpaceMode, ConvertOptions, Severity,
+};
+use eframe::egui;
+use std::{
+    any::Any,
+    fs::{self, File, OpenOptions},
+    io::{self, prelude::*, BufReader},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+};
+
+fn main() -> eframe::Result<()> {
+    let native_options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 560.0]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "bls2brs",
+        native_options,
+        Box::new(|_| Box::new(App::new())),
+    )
+}
+
+/// The options that can be changed per save. The rest come from the config.
+#[derive(Clone)]
+struct SaveOptions {
+    owner_name: String,
+    /// Empty to keep Blockland's owners, see `ConvertOptions::owner`.
+    owner_id: String,
+    dedup: bool,
+    merge_terrain: bool,
+    skip_water: bool,
+    color_space: ColorSpaceMode,
+}
+
+impl SaveOptions {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            owner_name: config.owner_name.clone().unwrap_or_default(),
+            owner_id: config.owner_id.map(|id| id.to_string()).unwrap_or_default(),
+            dedup: config.dedup.unwrap_or(false),
+            merge_terrain: config.merge_terrain.unwrap_or(false),
+            skip_water: config.skip_water.unwrap_or(false),
+            color_space: config.color_space.unwrap_or(ColorSpaceMode::Srgb),
+        }
+    }
+}
+
+enum Status {
+    Waiting,
+    Converting,
+    Converted,
+    Failed(String),
+}
+
+struct Save {
+    path: PathBuf,
+    options: SaveOptions,
+    status: Status,
+    /// Unknown brick names and how many bricks had them, most common first.
+    unknown: Vec<(String, usize)>,
+}
+
+/// Sent from the conversion thread.
+enum Message {
+    Log(String),
+    Finished {
+        index: usize,
+        result: Result<Vec<(String, usize)>, String>,
+    },
+}
+
+struct App {
+    config: Config,
+    /// Options for newly dropped saves.
+    defaults: SaveOptions,
+    saves: Vec<Save>,
+    selected: Option<usize>,
+    log: Vec<String>,
+    sender: mpsc::Sender<Message>,
+    receiver: mpsc::Receiver<Message>,
+}
+
+impl App {
+    fn new() -> Self {
+        let mut log = Vec::new();
+        let config = match Config::load() {
+            Ok(Some((path, config))) => {
+                log.push(format!("Using config {}", path.display()));
+                config
+            }
+            Ok(None) => Config::default(),
+            Err(e) => {
+                log.push(format!("Invalid config file: {}", e));
+                Config::default()
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            defaults: SaveOptions::from_config(&config),
+            config,
+            saves: Vec::new(),
+            selected: None,
+            log,
+            sender,
+            receiver,
+        }
+    }
+
+    fn is_converting(&self) -> bool {
+        self.saves
+            .iter()
+            .any(|save| matches!(save.status, Status::Converting))
+    }
+
+    fn receive(&mut self) {
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                Message::Log(line) => self.log.push(line),
+                Message::Finished { index, result } => {
+                    let save = &mut self.saves[index];
+                    match result {
+                        Ok(unknown) => {
+                            save.status = Status::Converted;
+                            save.unknown = unknown;
+                        }
+                        Err(e) => {
+                            self.log.push(format!(
+                                "Error converting {}: {}",
+                                save.path.display(),
+                                e
+                            ));
+                            save.status = Status::Failed(e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts the saves that haven't been, one at a time on another thread.
+    fn convert(&mut self, ctx: &egui::Context) {
+        let mut queue = Vec::new();
+        for (index, save) in self.saves.iter_mut().enumerate() {
+            if matches!(save.status, Status::Waiting | Status::Failed(_)) {
+                save.status = Status::Converting;
+                queue.push((index, save.path.clone(), save.options.clone()));
+            }
+        }
+
+        let config = self.config.clone();
+        let sender = self.sender.clone();
+        let ctx = ctx.clone();
+
+        thread::spawn(move || {
+            for (index, path, options) in queue {
+                let log = |line: String| {
+                    let _ = sender.send(Message::Log(line));
+                    ctx.request_repaint();
+                };
+                // A bug converting one save shouldn't leave the rest converting forever
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    convert_save(&path, &config, &options, &log)
+                }))
+                .unwrap_or_else(|payload| Err(panic_message(payload)));
+                let _ = sender.send(Message::Finished { index, result });
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    fn files_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Saves");
+        if self.saves.is_empty() {
+            ui.label("Drop .bls files onto this window.");
+        }
+
+        for (index, save) in self.saves.iter().enumerate() {
+            let name = save.path.file_name().unwrap_or_default().to_string_lossy();
+            let status = match &save.status {
+                Status::Waiting => "",
+                Status::Converting => " (converting)",
+                Status::Converted if save.unknown.is_empty() => " (done)",
+                Status::Converted => " (done, with unknown bricks)",
+                Status::Failed(_) => " (failed)",
+            };
+            let label = format!("{}{}", name, status);
+            if ui
+                .selectable_label(self.selected == Some(index), label)
+                .clicked()
+            {
+                self.selected = Some(index);
+            }
+        }
+    }
+
+    fn options_panel(&mut self, ui: &mut egui::Ui) {
+        let converting = self.is_converting();
+        let index = match self.selected {
+            Some(index) => index,
+            None => {
+                ui.label("Select a save to change its options.");
+                return;
+            }
+        };
+
+        let save = &mut self.saves[index];
+        ui.heading(save.path.file_name().unwrap_or_default().to_string_lossy());
+        if let Status::Failed(e) = &save.status {
+            ui.colored_label(egui::Color32::RED, e);
+        }
+
+        ui.add_enabled_ui(!converting, |ui| {
+            options_ui(ui, &mut save.options);
+        });
+
+        if ui
+            .add_enabled(
+                !converting,
+                egui::Button::new("Use these options for every save"),
+            )
+            .clicked()
+        {
+            let options = save.options.clone();
+            for save in &mut self.saves {
+                save.options = options.clone();
+            }
+            self.defaults = options;
+        }
+
+        ui.separator();
+        ui.heading("Unknown bricks");
+        let save = &self.saves[index];
+        match save.status {
+            Status::Converted if save.unknown.is_empty() => {
+                ui.label("Every brick was converted.");
+            }
+            Status::Converted => {
+                egui::ScrollArea::vertical()
+                    .id_source("unknown")
+                    .show(ui, |ui| {
+                        egui::Grid::new("unknown").striped(true).show(ui, |ui| {
+                            for (ui_name, count) in &save.unknown {
+                                ui.label(ui_name);
+                                ui.label(format!("{} bricks", count));
+                                ui.end_row();
+                            }
+                        });
+                    });
+            }
+            _ => {
+                ui.label("Shown after converting.");
+            }
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.receive();
+
+        let dropped: Vec<PathBuf> = ctx.input(|input| {
+            input
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        for path in dropped {
+            if !self.saves.iter().any(|save| save.path == path) {
+                self.saves.push(Save {
+                    path,
+                    options: self.defaults.clone(),
+                    status: Status::Waiting,
+                    unknown: Vec::new(),
+                });
+                self.selected = Some(self.saves.len() - 1);
+            }
+        }
+
+        let converting = self.is_converting();
+
+        egui::TopBottomPanel::top("actions").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let waiting = self
+                    .saves
+                    .iter()
+                    .any(|save| matches!(save.status, Status::Waiting | Status::Failed(_)));
+                if ui
+                    .add_enabled(!converting && waiting, egui::Button::new("Convert"))
+                    .clicked()
+                {
+                    self.convert(ctx);
+                }
+                if ui
+                    .add_enabled(!converting, egui::Button::new("Clear"))
+                    .clicked()
+                {
+                    self.saves.clear();
+                    self.selected = None;
+                }
+            });
+        });
+
+        egui::TopBottomPanel::bottom("log")
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &self.log {
+                            ui.monospace(line);
+                        }
+                    });
+            });
+
+        egui::SidePanel::left("saves").show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| self.files_panel(ui));
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| self.options_panel(ui));
+    }
+}
+
+fn options_ui(ui: &mut egui::Ui, options: &mut SaveOptions) {
+    ui.label("Owner of every brick, empty to keep Blockland's owners:");
+    egui::Grid::new("owner").show(ui, |ui| {
+        ui.label("Name");
+        ui.text_edit_singleline(&mut options.owner_name);
+        ui.end_row();
+        ui.label("UUID");
+        ui.text_edit_singleline(&mut options.owner_id);
+        ui.end_row();
+    });
+
+    ui.checkbox(&mut options.dedup, "Remove duplicate bricks");
+    ui.checkbox(&mut options.merge_terrain, "Merge terrain cubes");
+    ui.checkbox(&mut options.skip_water, "Leave out water");
+
+    ui.horizontal(|ui| {
+        ui.label("Colors:");
+        ui.radio_value(&mut options.color_space, ColorSpaceMode::Srgb, "sRGB");
+        ui.radio_value(
+            &mut options.color_space,
+            ColorSpaceMode::Legacy,
+            "bls2brs 0.3 and earlier",
+        );
+    });
+}
+
+/// Converts one save to a brs file next to it, returning its unknown bricks. An existing
+/// file is handled as the config's `on_existing` says, except that there's nobody to ask,
+/// so `ask` renames like `rename`.
+fn convert_save(
+    path: &Path,
+    config: &Config,
+    save_options: &SaveOptions,
+    log: &impl Fn(String),
+) -> Result<Vec<(String, usize)>, String> {
+    log(format!("Converting {}", path.display()));
+
+    let mut options = ConvertOptions::default();
+    if let Some(user_name) = std::env::var_os("USER").or_else(|| std::env::var_os("USERNAME")) {
+        options.author.name = user_name.to_string_lossy().into_owned();
+    }
+    config.apply(&mut options);
+    if let Some(owners_path) = &config.owners {
+        let source = errmsg(
+            fs::read_to_string(owners_path),
+            "Failed to read owners file",
+        )?;
+        options.owners = errmsg(OwnerMap::from_toml(&source), "Invalid owners file")?;
+    }
+
+    options.dedup = save_options.dedup;
+    options.merge_terrain = save_options.merge_terrain;
+    options.skip_water = save_options.skip_water;
+    options.color_space = save_options.color_space;
+    if !save_options.owner_id.trim().is_empty() {
+        let id = errmsg(
+            brs::uuid::Uuid::parse_str(save_options.owner_id.trim()),
+            "Invalid owner UUID",
+        )?;
+        let name = match save_options.owner_name.trim() {
+            "" => options.author.name.clone(),
+            name => name.to_string(),
+        };
+        options.owner = Some(brs::User { id, name });
+    }
+
+    let mut reader = BufReader::new(errmsg(File::open(path), "Failed to open bls file")?);
+    let (mut input, output_path): (Box<dyn BufRead>, _) =
+        match errmsg(archive::detect(&mut reader), "Failed to read bls file")? {
+            Compression::None => (Box::new(reader), path.with_extension("brs")),
+            Compression::Gzip => (
+                Box::new(archive::gunzip(reader)),
+                path.with_extension("").with_extension("brs"),
+            ),
+            Compression::Zip => return Err(String::from(
+                "Zip archives can't be converted here, extract them or use bls2brs from a terminal",
+            )),
+        };
+
+    let overwrite = match config.on_existing.as_deref() {
+        Some("overwrite") => true,
+        Some("skip") if output_path.exists() => {
+            return Err(format!("Skipped, {} already exists", output_path.display()))
+        }
+        _ => false,
+    };
+
+    errmsg(format::detect(&mut input), "Failed to read bls file")?;
+    let reader = errmsg(bl_save::Reader::new(input), "Failed to read bls file")?;
+    let mut converted = errmsg(
+        convert_with_options(reader, &options),
+        "Failed to convert bls file",
+    )?;
+
+    if let Some(file_name) = path.file_name() {
+        let mut prefix = format!(
+            "Converted from {} with bls2brs.",
+            file_name.to_string_lossy()
+        );
+        if !converted.write_data.description.is_empty() {
+            prefix.push('\n');
+        }
+        converted.write_data.description.insert_str(0, &prefix);
+    }
+
+    let (mut output_file, output_path) = if overwrite {
+        let file = errmsg(File::create(&output_path), "Failed to create BRS file")?;
+        (file, output_path)
+    } else {
+        errmsg(create_numbered(&output_path), "Failed to create BRS file")?
+    };
+    errmsg(
+        brs::write_save(&mut output_file, &converted.write_data),
+        "Failed to write BRS file",
+    )?;
+
+    log(format!(
+        "{} of {} bricks converted to {} bricks, written to {}",
+        converted.count_success,
+        converted.count_success + converted.count_failure,
+        converted.write_data.bricks.len(),
+        output_path.display()
+    ));
+
+    // Approximations and the like are in every save, only show what needs fixing
+    for warning in &converted.warnings {
+        if warning.severity() >= Severity::Warning {
+            log(format!("  {}", warning));
+        }
+    }
+
+    let mut unknown: Vec<_> = converted.unknown_ui_names.into_iter().collect();
+    unknown.sort_by(|(_, a), (_, b)| a.cmp(b).reverse());
+    Ok(unknown)
+}
+
+/// Creates `path`, or the first of `foo (2).brs`, `foo (3).brs`... that doesn't exist yet.
+fn create_numbered(path: &Path) -> io::Result<(File, PathBuf)> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+    let numbered = (2..).map(|n| path.with_file_name(format!("{} ({}).{}", stem, n, extension)));
+    for path in std::iter::once(path.to_path_buf()).chain(numbered) {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    format!("bls2brs crashed converting this save: {}", message)
+}
+
+fn errmsg<T, E: std::fmt::Display>(r: Result<T, E>, message_prefix: &str) -> Result<T, String> {
+    r.map_err(|e| format!("{}: {}", message_prefix, e))
+}
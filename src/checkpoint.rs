@@ -0,0 +1,308 @@
+//! Saving the progress of a conversion, so a huge save can be picked up where it was left
+//! if the process dies. See `ConvertOptions::checkpoint`.
+//!
+//! The directory holds `checkpoint.json`, which says how far the conversion got, and a
+//! `batch-N.json` file with the bricks mapped since the checkpoint before it. Besides
+//! the bricks and the tables they index into, it keeps everything the report counts per
+//! source brick, so a resumed report covers the whole save. Provenance isn't kept, so
+//! checkpoints can't be used with it.
+//!
+//! Progress is only picked up with the options it was saved with, see `options_hash`.
+
+use crate::{
+    json::{BrickJson, UserJson},
+    AssetCount, ConvertOptions, DroppedPrint, FailureReason, GeometryIssue, MinigameBrick,
+    MusicBrick, NamedBrick, Placeholder, Portal, PrintPlacement,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::{self, prelude::*, BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+/// The current `version` of `checkpoint.json`.
+const VERSION: u32 = 2;
+
+const STATE_FILE: &str = "checkpoint.json";
+
+/// Where and how often to save progress.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    /// Created if missing. Holds the progress of one save at a time.
+    pub dir: PathBuf,
+    /// Source bricks to convert between checkpoints.
+    pub batch_size: usize,
+}
+
+/// Source bricks between checkpoints by default.
+pub const DEFAULT_BATCH_SIZE: usize = 250_000;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct State {
+    version: u32,
+    /// The save's description and brick count, to tell it's the same save.
+    pub description: String,
+    pub brick_count: Option<usize>,
+    /// See `options_hash`.
+    pub options_hash: String,
+    /// Source bricks before this one are in the batches.
+    pub next_source_index: usize,
+    pub batches: usize,
+    pub brick_assets: Vec<String>,
+    pub colors: Vec<[u8; 4]>,
+    pub materials: Vec<String>,
+    pub brick_owners: Vec<UserJson>,
+    pub owner_map: Vec<(u32, u32)>,
+    /// Indices into the bricks of all batches, see `ConvertOptions::strip_baseplates`.
+    pub baseplates: Vec<usize>,
+    pub unknown_ui_names: HashMap<String, usize>,
+    pub rejected_ui_names: HashMap<String, usize>,
+    pub substituted_assets: HashMap<String, String>,
+    pub tally: Tally,
+}
+
+/// What the report counts per source brick, as far as the conversion got.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Tally {
+    pub count_success: usize,
+    pub count_failure: usize,
+    pub failure_reasons: HashMap<FailureReason, usize>,
+    pub count_out_of_range: usize,
+    pub count_owner_filtered: usize,
+    pub count_invisible_skipped: usize,
+    pub count_water_skipped: usize,
+    pub count_noncolliding_skipped: usize,
+    pub count_unknown_placeholders: usize,
+    pub count_parse_errors: usize,
+    pub count_dropped_events: usize,
+    pub count_water_fx: usize,
+    pub count_water_fx_skipped: usize,
+    pub count_undulo: usize,
+    pub placeholders: Vec<Placeholder>,
+    pub dropped_prints: Vec<DroppedPrint>,
+    pub geometry_checked: HashSet<String>,
+    pub geometry_issues: Vec<GeometryIssue>,
+    pub prints: Vec<PrintPlacement>,
+    pub named_bricks: Vec<NamedBrick>,
+    pub music_bricks: Vec<MusicBrick>,
+    pub portals: Vec<Portal>,
+    pub minigame_bricks: Vec<MinigameBrick>,
+    pub approximate_ui_names: HashMap<String, usize>,
+    pub animated_fx: HashMap<String, usize>,
+    pub asset_counts: Vec<AssetCount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Batch {
+    bricks: Vec<BrickJson>,
+    /// Bricks written after all others, see `BrickDesc::non_priority`.
+    non_priority: Vec<BrickJson>,
+}
+
+/// Everything saved by the checkpoints so far.
+pub(crate) struct Resumed {
+    pub state: State,
+    pub bricks: Vec<brs::Brick>,
+    pub non_priority: Vec<brs::Brick>,
+}
+
+impl Checkpoint {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Reads the progress saved for this save, if any. Fails if the directory holds the
+    /// progress of a different save, or of this one converted with other options.
+    pub(crate) fn load(
+        &self,
+        description: &str,
+        brick_count: Option<usize>,
+        options_hash: &str,
+    ) -> io::Result<Option<Resumed>> {
+        let state: State = match File::open(self.dir.join(STATE_FILE)) {
+            Ok(file) => serde_json::from_reader(BufReader::new(file)).map_err(invalid)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        if state.version != VERSION {
+            return Err(invalid(format!(
+                "unsupported checkpoint version {}",
+                state.version
+            )));
+        }
+        if state.description != description || state.brick_count != brick_count {
+            return Err(invalid(format!(
+                "{} holds the progress of a different save",
+                self.dir.display()
+            )));
+        }
+        if state.options_hash != options_hash {
+            return Err(invalid(format!(
+                "{} holds progress converted with other options, convert with the same options or remove it",
+                self.dir.display()
+            )));
+        }
+
+        let mut bricks = Vec::new();
+        let mut non_priority = Vec::new();
+        for index in 0..state.batches {
+            let file = File::open(self.batch_path(index))?;
+            let batch: Batch = serde_json::from_reader(BufReader::new(file)).map_err(invalid)?;
+            for brick in batch.bricks {
+                bricks.push(brick.into_brick().map_err(invalid)?);
+            }
+            for brick in batch.non_priority {
+                non_priority.push(brick.into_brick().map_err(invalid)?);
+            }
+        }
+
+        Ok(Some(Resumed {
+            state,
+            bricks,
+            non_priority,
+        }))
+    }
+
+    /// Writes the bricks mapped since the last checkpoint as the next batch, then
+    /// `state` with `state.batches` counting it.
+    pub(crate) fn save(
+        &self,
+        state: &mut State,
+        bricks: &[brs::Brick],
+        non_priority: &[brs::Brick],
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let batch = Batch {
+            bricks: bricks.iter().map(BrickJson::from_brick).collect(),
+            non_priority: non_priority.iter().map(BrickJson::from_brick).collect(),
+        };
+        write_json(&self.batch_path(state.batches), &batch)?;
+
+        state.batches += 1;
+        write_json(&self.dir.join(STATE_FILE), state)
+    }
+
+    /// Removes the saved progress, once the converted save is written.
+    pub fn clear(&self) -> io::Result<()> {
+        let state: State = match File::open(self.dir.join(STATE_FILE)) {
+            Ok(file) => serde_json::from_reader(BufReader::new(file)).map_err(invalid)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        // The batch after the last may have been written before the state was
+        for index in 0..=state.batches {
+            match fs::remove_file(self.batch_path(index)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        fs::remove_file(self.dir.join(STATE_FILE))
+    }
+
+    fn batch_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("batch-{}.json", index))
+    }
+}
+
+impl State {
+    pub(crate) fn new(description: &str, brick_count: Option<usize>, options_hash: &str) -> Self {
+        Self {
+            version: VERSION,
+            description: description.to_string(),
+            brick_count,
+            options_hash: options_hash.to_string(),
+            next_source_index: 0,
+            batches: 0,
+            brick_assets: Vec::new(),
+            colors: Vec::new(),
+            materials: Vec::new(),
+            brick_owners: Vec::new(),
+            owner_map: Vec::new(),
+            baseplates: Vec::new(),
+            unknown_ui_names: HashMap::new(),
+            rejected_ui_names: HashMap::new(),
+            substituted_assets: HashMap::new(),
+            tally: Tally::default(),
+        }
+    }
+}
+
+/// Hashes the options that change how source bricks are converted. The passes after the
+/// main loop run on every brick once the conversion is done, so they don't count. A
+/// `color_mapper` can't be hashed, only whether there is one.
+pub(crate) fn options_hash(options: &ConvertOptions) -> String {
+    fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+        items.sort();
+        items
+    }
+
+    let owners = sorted(
+        options
+            .owners
+            .owners
+            .iter()
+            .map(|(bl_id, user)| (*bl_id, user.id.to_string(), user.name.clone()))
+            .collect(),
+    );
+    let parts = [
+        format!("{:?}", options.materials),
+        format!("{:?}", options.glass_alpha_threshold),
+        format!("{:?}", options.color_space),
+        format!("{:?}", options.color_mapper.is_some()),
+        format!("{:?}", options.curated_colors),
+        format!("{:?}", options.animated_fx),
+        format!("{:?}", options.coordinate_limit),
+        format!("{:?}", options.out_of_range),
+        format!("{:?}", options.scale),
+        format!("{:?}", options.target),
+        format!("{:?} {:?}", owners, options.owners.fallback),
+        format!(
+            "{:?}",
+            options
+                .owner
+                .as_ref()
+                .map(|user| (user.id.to_string(), &user.name))
+        ),
+        format!("{:?}", sorted(options.only_owners.iter().collect())),
+        format!("{:?}", options.skip_invisible),
+        format!("{:?}", options.skip_noncolliding),
+        format!("{:?}", options.skip_water),
+        format!("{:?}", options.placeholders),
+        format!("{:?}", options.placeholder_unknown),
+        format!("{:?}", options.check_geometry),
+        format!("{:?}", options.lenient),
+        format!("{:?}", options.strict),
+        format!("{:?}", sorted(options.known_assets.iter().collect())),
+        format!("{:?}", sorted(options.print_assets.iter().collect())),
+    ];
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Writes to a temporary file first, so a crash can't leave a half-written file.
+fn write_json(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    let mut writer = BufWriter::new(File::create(&temp_path)?);
+    serde_json::to_writer(&mut writer, value).map_err(io::Error::from)?;
+    writer.flush()?;
+    drop(writer);
+    fs::rename(temp_path, path)
+}
+
+fn invalid(e: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
@@ -0,0 +1,149 @@
+//! How Blockland colors become Brickadia colors, see `ConvertOptions::color_mapper`.
+
+use crate::ColorSpaceMode;
+
+/// Turns a Blockland color, RGBA from 0 to 1 as stored in the save, into the color
+/// written to the Brickadia palette.
+///
+/// Each color of the save's colorset is mapped once, so bricks keep their colorset
+/// index in the converted save.
+pub trait ColorMapper {
+    fn map(&self, color: (f32, f32, f32, f32)) -> brs::Color;
+}
+
+/// The default conversion, from sRGB to the linear colors Brickadia stores.
+impl ColorMapper for ColorSpaceMode {
+    fn map(&self, color: (f32, f32, f32, f32)) -> brs::Color {
+        crate::map_color(color, *self)
+    }
+}
+
+/// Writes the colors unchanged, for palettes made to match Blockland's colors by eye.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExactSrgb;
+
+impl ColorMapper for ExactSrgb {
+    fn map(&self, (r, g, b, a): (f32, f32, f32, f32)) -> brs::Color {
+        brs::Color::from_rgba(
+            crate::unit_to_byte(r),
+            crate::unit_to_byte(g),
+            crate::unit_to_byte(b),
+            crate::unit_to_byte(a),
+        )
+    }
+}
+
+/// Converts colors with `color_space`, then replaces each with the closest color of a
+/// fixed palette, such as a server's colorset.
+#[derive(Debug, Clone)]
+pub struct Quantize {
+    palette: Vec<brs::Color>,
+    color_space: ColorSpaceMode,
+}
+
+impl Quantize {
+    /// Fails if `palette` is empty.
+    pub fn new(palette: Vec<brs::Color>, color_space: ColorSpaceMode) -> Option<Self> {
+        if palette.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            palette,
+            color_space,
+        })
+    }
+
+    /// The index in the palette of the color closest to `color`, compared as RGBA bytes.
+    pub fn nearest(&self, color: brs::Color) -> usize {
+        let distance = |other: &brs::Color| {
+            [
+                (color.r(), other.r()),
+                (color.g(), other.g()),
+                (color.b(), other.b()),
+                (color.a(), other.a()),
+            ]
+            .iter()
+            .map(|(x, y)| (i32::from(*x) - i32::from(*y)).pow(2))
+            .sum::<i32>()
+        };
+
+        self.palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, other)| distance(other))
+            .map_or(0, |(index, _)| index)
+    }
+}
+
+impl ColorMapper for Quantize {
+    fn map(&self, color: (f32, f32, f32, f32)) -> brs::Color {
+        self.palette[self.nearest(self.color_space.map(color))]
+    }
+}
+
+/// The first colors of Blockland's stock colorset: red, yellow, green, blue, the four
+/// grays and brown. Every stock colorset starts with these, so a save that does too is
+/// taken to use it.
+const STOCK_COLORS: &[(f32, f32, f32, f32)] = &[
+    (0.898039, 0.0, 0.0, 1.0),
+    (0.898039, 0.898039, 0.0, 1.0),
+    (0.0, 0.498039, 0.247059, 1.0),
+    (0.2, 0.0, 0.8, 1.0),
+    (0.898039, 0.898039, 0.898039, 1.0),
+    (0.74902, 0.74902, 0.74902, 1.0),
+    (0.498039, 0.498039, 0.498039, 1.0),
+    (0.2, 0.2, 0.2, 1.0),
+    (0.392157, 0.192157, 0.0, 1.0),
+];
+
+/// Brickadia colors picked by hand for each of `STOCK_COLORS`. The plain conversion is
+/// right by the numbers but looks washed out under Brickadia's lighting.
+const CURATED_COLORS: &[[u8; 4]] = &[
+    [160, 0, 0, 255],
+    [200, 160, 0, 255],
+    [0, 60, 14, 255],
+    [6, 0, 140, 255],
+    [180, 180, 180, 255],
+    [110, 110, 110, 255],
+    [45, 45, 45, 255],
+    [8, 8, 8, 255],
+    [30, 8, 0, 255],
+];
+
+/// Whether a save's colorset is Blockland's stock one, see `ConvertOptions::curated_colors`.
+pub fn is_stock_colorset(colors: &[(f32, f32, f32, f32)]) -> bool {
+    colors.len() >= STOCK_COLORS.len()
+        && colors
+            .iter()
+            .zip(STOCK_COLORS)
+            .all(|(color, stock)| same_color(*color, *stock))
+}
+
+/// Saves store colors with six decimals, so allow for rounding.
+fn same_color(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) -> bool {
+    let close = |x: f32, y: f32| (x - y).abs() < 0.5 / 255.0;
+    close(a.0, b.0) && close(a.1, b.1) && close(a.2, b.2) && close(a.3, b.3)
+}
+
+/// Writes the hand-picked color for each stock Blockland color and converts other
+/// colors with `color_space`.
+#[derive(Debug, Clone, Copy)]
+pub struct Curated {
+    pub color_space: ColorSpaceMode,
+}
+
+impl ColorMapper for Curated {
+    fn map(&self, color: (f32, f32, f32, f32)) -> brs::Color {
+        match STOCK_COLORS
+            .iter()
+            .position(|stock| same_color(color, *stock))
+        {
+            Some(index) => {
+                let [r, g, b, a] = CURATED_COLORS[index];
+                brs::Color::from_rgba(r, g, b, a)
+            }
+            None => self.color_space.map(color),
+        }
+    }
+}
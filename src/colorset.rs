@@ -0,0 +1,135 @@
+//! Blockland colorsets (`colorSet.txt`) and Brickadia color palette presets.
+
+use crate::ColorSpaceMode;
+use std::io::{self, prelude::*};
+
+pub struct ColorGroup {
+    pub name: String,
+    pub colors: Vec<brs::Color>,
+}
+
+/// Reads a Blockland colorset, converting every color the same way `convert` does.
+///
+/// Colors may be given as floats (`0.5 0.5 0.5 1`) or bytes (`128 128 128 255`).
+/// A `DIV:Name` line ends the current column and names it.
+pub fn read_colorset(
+    reader: impl BufRead,
+    color_space: ColorSpaceMode,
+) -> io::Result<Vec<ColorGroup>> {
+    let mut groups = Vec::new();
+    let mut colors = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("DIV:") {
+            groups.push(ColorGroup {
+                name: name.trim().to_string(),
+                colors: std::mem::take(&mut colors),
+            });
+            continue;
+        }
+
+        let color = parse_color(line).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid colorset line {:?}", line),
+            )
+        })?;
+
+        colors.push(crate::map_color(color, color_space));
+    }
+
+    if !colors.is_empty() {
+        groups.push(ColorGroup {
+            name: format!("Group {}", groups.len() + 1),
+            colors,
+        });
+    }
+
+    Ok(groups)
+}
+
+fn parse_color(line: &str) -> Option<(f32, f32, f32, f32)> {
+    let values = line
+        .split_whitespace()
+        .map(|s| s.parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let (r, g, b, a) = match values.as_slice() {
+        [r, g, b] => (*r, *g, *b, None),
+        [r, g, b, a] => (*r, *g, *b, Some(*a)),
+        _ => return None,
+    };
+
+    // Byte colorsets use 0-255 per channel, and are opaque without an alpha
+    if r > 1.0 || g > 1.0 || b > 1.0 || a.is_some_and(|a| a > 1.0) {
+        Some((
+            r / 255.0,
+            g / 255.0,
+            b / 255.0,
+            a.map_or(1.0, |a| a / 255.0),
+        ))
+    } else {
+        Some((r, g, b, a.unwrap_or(1.0)))
+    }
+}
+
+/// Writes `groups` as a Brickadia color palette preset.
+pub fn write_palette_preset(mut w: impl Write, groups: &[ColorGroup]) -> io::Result<()> {
+    writeln!(w, "{{")?;
+    writeln!(w, "\t\"formatVersion\": \"1\",")?;
+    writeln!(w, "\t\"presetVersion\": \"1\",")?;
+    writeln!(w, "\t\"type\": \"ColorPalette\",")?;
+    writeln!(w, "\t\"data\": {{")?;
+    writeln!(w, "\t\t\"groups\": [")?;
+
+    for (i, group) in groups.iter().enumerate() {
+        writeln!(w, "\t\t\t{{")?;
+        writeln!(w, "\t\t\t\t\"name\": {},", json_string(&group.name))?;
+        writeln!(w, "\t\t\t\t\"colors\": [")?;
+
+        for (j, color) in group.colors.iter().enumerate() {
+            let comma = if j + 1 < group.colors.len() { "," } else { "" };
+            writeln!(
+                w,
+                "\t\t\t\t\t{{ \"r\": {}, \"g\": {}, \"b\": {}, \"a\": {} }}{}",
+                color.r(),
+                color.g(),
+                color.b(),
+                color.a(),
+                comma
+            )?;
+        }
+
+        writeln!(w, "\t\t\t\t]")?;
+        let comma = if i + 1 < groups.len() { "," } else { "" };
+        writeln!(w, "\t\t\t}}{}", comma)?;
+    }
+
+    writeln!(w, "\t\t]")?;
+    writeln!(w, "\t}}")?;
+    writeln!(w, "}}")?;
+
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
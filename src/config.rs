@@ -0,0 +1,366 @@
+//! Persistent defaults from a `bls2brs.toml` file, for users who can't pass flags.
+
+use crate::{pipeline::Pipeline, AnimatedFx, ColorSpaceMode, ConvertOptions, Scale, Target};
+use brs::uuid::Uuid;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub const FILE_NAME: &str = "bls2brs.toml";
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub author: Option<String>,
+    pub author_id: Option<Uuid>,
+    /// With `owner_id`, owns every converted brick, see `ConvertOptions::owner`.
+    pub owner_name: Option<String>,
+    pub owner_id: Option<Uuid>,
+    pub map: Option<String>,
+    /// Append a summary of the conversion to the save description.
+    pub description_summary: Option<bool>,
+    /// Where converted files go instead of next to their input.
+    pub output_dir: Option<PathBuf>,
+    /// Where converted files are kept to skip converting the same input again.
+    pub cache_dir: Option<PathBuf>,
+    /// An owners file, see `owners::OwnerMap::from_toml`.
+    pub owners: Option<PathBuf>,
+    /// A file of brick asset names for `ConvertOptions::known_assets`, see
+    /// `assets::parse_list`.
+    pub asset_list: Option<PathBuf>,
+    /// `"brs"` or `"json"`.
+    pub output_format: Option<String>,
+    /// `"ask"`, `"overwrite"`, `"skip"` or `"rename"`, for when the output file exists.
+    pub on_existing: Option<String>,
+    /// Largest brick count to write without `--force`, 0 for no limit.
+    pub max_bricks: Option<usize>,
+    /// Largest estimated file size in megabytes to write without `--force`, 0 for no limit.
+    pub max_size_mb: Option<u64>,
+    /// See `ConvertOptions::memory_limit`, 0 for no limit.
+    pub max_memory_mb: Option<u64>,
+    pub color_space: Option<ColorSpaceMode>,
+    pub curated_colors: Option<bool>,
+    pub animated_fx: Option<AnimatedFx>,
+    pub scale: Option<Scale>,
+    pub target: Option<Target>,
+    pub glass_alpha_threshold: Option<f32>,
+    pub dedup: Option<bool>,
+    pub strip_baseplates: Option<bool>,
+    pub add_baseplate: Option<bool>,
+    pub skip_invisible: Option<bool>,
+    pub skip_noncolliding: Option<bool>,
+    pub skip_water: Option<bool>,
+    pub merge_terrain: Option<bool>,
+    /// Pass names for `Pipeline::from_specs`, checked by `from_toml`.
+    pub passes: Option<Vec<String>>,
+    pub detect_overlaps: Option<bool>,
+    /// Tolerance in units, see `ConvertOptions::grid_snap`.
+    pub snap: Option<u32>,
+    pub provenance: Option<bool>,
+    pub placeholders: Option<bool>,
+    pub placeholder_unknown: Option<bool>,
+    pub strict: Option<bool>,
+    pub lenient: Option<bool>,
+    /// Added to `ConvertOptions::print_assets`.
+    pub print_assets: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    author: Option<String>,
+    author_id: Option<String>,
+    owner_name: Option<String>,
+    owner_id: Option<String>,
+    map: Option<String>,
+    description_summary: Option<bool>,
+    output_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    owners: Option<PathBuf>,
+    asset_list: Option<PathBuf>,
+    to: Option<String>,
+    on_existing: Option<String>,
+    max_bricks: Option<usize>,
+    max_size_mb: Option<u64>,
+    max_memory_mb: Option<u64>,
+    color_space: Option<String>,
+    curated_colors: Option<bool>,
+    animated_fx: Option<String>,
+    scale: Option<String>,
+    target: Option<String>,
+    glass_alpha_threshold: Option<f32>,
+    dedup: Option<bool>,
+    strip_baseplates: Option<bool>,
+    add_baseplate: Option<bool>,
+    skip_invisible: Option<bool>,
+    skip_noncolliding: Option<bool>,
+    skip_water: Option<bool>,
+    merge_terrain: Option<bool>,
+    passes: Option<Vec<String>>,
+    overlaps: Option<bool>,
+    snap: Option<u32>,
+    provenance: Option<bool>,
+    placeholders: Option<bool>,
+    placeholder_unknown: Option<bool>,
+    strict: Option<bool>,
+    lenient: Option<bool>,
+    #[serde(default)]
+    print_assets: HashMap<String, String>,
+}
+
+impl Config {
+    /// Parses a config file. Relative paths in it are resolved against `base_dir`.
+    ///
+    /// ```toml
+    /// author = "Zeblote"
+    /// author_id = "a1b16aca-9627-4a16-a160-67fa9adbb7b6"
+    /// owner_name = "Zeblote" # defaults to author
+    /// owner_id = "a1b16aca-9627-4a16-a160-67fa9adbb7b6"
+    /// map = "Plate"
+    /// description_summary = true
+    /// output_dir = "converted"
+    /// cache_dir = "cache"
+    /// owners = "owners.toml"
+    /// asset_list = "assets.txt"
+    /// to = "brs" # or "json"
+    /// on_existing = "ask" # or "overwrite", "skip", "rename"
+    /// max_bricks = 1000000
+    /// max_size_mb = 0
+    /// max_memory_mb = 0
+    /// color_space = "srgb" # or "legacy"
+    /// curated_colors = false
+    /// animated_fx = "plastic" # or "glow", "mark"
+    /// scale = "normal" # or "micro"
+    /// target = "latest" # or "a4", "a5"
+    /// glass_alpha_threshold = 1.0
+    /// dedup = true
+    /// strip_baseplates = false
+    /// add_baseplate = false
+    /// skip_invisible = false
+    /// skip_noncolliding = false
+    /// skip_water = false
+    /// merge_terrain = false
+    /// passes = ["recenter", "clip=-5000,-5000,0,5000,5000,2000"]
+    /// overlaps = false
+    /// snap = 2
+    /// provenance = false
+    /// placeholders = false
+    /// placeholder_unknown = false
+    /// strict = false
+    /// lenient = false
+    ///
+    /// [print_assets]
+    /// "2x2f/letterA" = "PB_DefaultTile"
+    /// ```
+    pub fn from_toml(source: &str, base_dir: &Path) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(source).map_err(|e| e.to_string())?;
+
+        let color_space = match file.color_space.as_deref() {
+            None => None,
+            Some("srgb") => Some(ColorSpaceMode::Srgb),
+            Some("legacy") => Some(ColorSpaceMode::Legacy),
+            Some(other) => return Err(format!("unknown color_space {:?}", other)),
+        };
+
+        let animated_fx = match file.animated_fx.as_deref() {
+            None => None,
+            Some(mode) => Some(
+                AnimatedFx::parse(mode).ok_or_else(|| format!("unknown animated_fx {:?}", mode))?,
+            ),
+        };
+
+        let scale = match file.scale.as_deref() {
+            None => None,
+            Some("normal") => Some(Scale::Normal),
+            Some("micro") => Some(Scale::Micro),
+            Some(other) => return Err(format!("unknown scale {:?}", other)),
+        };
+
+        let target = match file.target.as_deref() {
+            None => None,
+            Some(target) => {
+                Some(Target::parse(target).ok_or_else(|| format!("unknown target {:?}", target))?)
+            }
+        };
+
+        let author_id = match file.author_id {
+            Some(id) => {
+                Some(Uuid::parse_str(&id).map_err(|e| format!("invalid author_id: {}", e))?)
+            }
+            None => None,
+        };
+
+        let owner_id = match file.owner_id {
+            Some(id) => Some(Uuid::parse_str(&id).map_err(|e| format!("invalid owner_id: {}", e))?),
+            None => None,
+        };
+
+        if let Some(passes) = &file.passes {
+            Pipeline::from_specs(passes)?;
+        }
+
+        Ok(Self {
+            author: file.author,
+            author_id,
+            owner_name: file.owner_name,
+            owner_id,
+            map: file.map,
+            description_summary: file.description_summary,
+            output_dir: file.output_dir.map(|path| base_dir.join(path)),
+            cache_dir: file.cache_dir.map(|path| base_dir.join(path)),
+            owners: file.owners.map(|path| base_dir.join(path)),
+            asset_list: file.asset_list.map(|path| base_dir.join(path)),
+            output_format: file.to,
+            on_existing: file.on_existing,
+            max_bricks: file.max_bricks,
+            max_size_mb: file.max_size_mb,
+            max_memory_mb: file.max_memory_mb,
+            color_space,
+            curated_colors: file.curated_colors,
+            animated_fx,
+            scale,
+            target,
+            glass_alpha_threshold: file.glass_alpha_threshold,
+            dedup: file.dedup,
+            strip_baseplates: file.strip_baseplates,
+            add_baseplate: file.add_baseplate,
+            skip_invisible: file.skip_invisible,
+            skip_noncolliding: file.skip_noncolliding,
+            skip_water: file.skip_water,
+            merge_terrain: file.merge_terrain,
+            passes: file.passes,
+            detect_overlaps: file.overlaps,
+            snap: file.snap,
+            provenance: file.provenance,
+            placeholders: file.placeholders,
+            placeholder_unknown: file.placeholder_unknown,
+            strict: file.strict,
+            lenient: file.lenient,
+            print_assets: file.print_assets,
+        })
+    }
+
+    /// Finds and reads the first config file in the working directory, next to the
+    /// executable, or in the user's config directory. `Ok(None)` if there is none.
+    pub fn load() -> Result<Option<(PathBuf, Self)>, String> {
+        for path in search_paths() {
+            let source = match fs::read_to_string(&path) {
+                Ok(source) => source,
+                Err(_) => continue,
+            };
+            let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let config = Self::from_toml(&source, base_dir)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            return Ok(Some((path, config)));
+        }
+
+        Ok(None)
+    }
+
+    /// Sets the options this config has values for.
+    pub fn apply(&self, options: &mut ConvertOptions) {
+        if let Some(author) = &self.author {
+            options.author.name = author.clone();
+        }
+        if let Some(author_id) = self.author_id {
+            options.author.id = author_id;
+        }
+        if let Some(map) = &self.map {
+            options.map = map.clone();
+        }
+        if let Some(max) = self.max_memory_mb {
+            options.memory_limit = Some(max.saturating_mul(1_000_000)).filter(|&max| max > 0);
+        }
+        if let Some(color_space) = self.color_space {
+            options.color_space = color_space;
+        }
+        if let Some(curated_colors) = self.curated_colors {
+            options.curated_colors = curated_colors;
+        }
+        if let Some(animated_fx) = self.animated_fx {
+            options.animated_fx = animated_fx;
+        }
+        if let Some(scale) = self.scale {
+            options.scale = scale;
+        }
+        if let Some(target) = self.target {
+            options.target = target;
+        }
+        if let Some(threshold) = self.glass_alpha_threshold {
+            options.glass_alpha_threshold = threshold;
+        }
+        if let Some(dedup) = self.dedup {
+            options.dedup = dedup;
+        }
+        if let Some(strip_baseplates) = self.strip_baseplates {
+            options.strip_baseplates = strip_baseplates;
+        }
+        if let Some(add_baseplate) = self.add_baseplate {
+            options.add_baseplate = add_baseplate;
+        }
+        if let Some(skip_invisible) = self.skip_invisible {
+            options.skip_invisible = skip_invisible;
+        }
+        if let Some(skip_noncolliding) = self.skip_noncolliding {
+            options.skip_noncolliding = skip_noncolliding;
+        }
+        if let Some(skip_water) = self.skip_water {
+            options.skip_water = skip_water;
+        }
+        if let Some(merge_terrain) = self.merge_terrain {
+            options.merge_terrain = merge_terrain;
+        }
+        if let Some(Ok(pipeline)) = self.passes.as_deref().map(Pipeline::from_specs) {
+            options.pipeline = pipeline;
+        }
+        if let Some(detect_overlaps) = self.detect_overlaps {
+            options.detect_overlaps = detect_overlaps;
+        }
+        if let Some(snap) = self.snap {
+            options.grid_snap = Some(snap);
+        }
+        if let Some(provenance) = self.provenance {
+            options.provenance = provenance;
+        }
+        if let Some(placeholders) = self.placeholders {
+            options.placeholders = placeholders;
+        }
+        if let Some(placeholder_unknown) = self.placeholder_unknown {
+            options.placeholder_unknown = placeholder_unknown;
+        }
+        if let Some(strict) = self.strict {
+            options.strict = strict;
+        }
+        if let Some(lenient) = self.lenient {
+            options.lenient = lenient;
+        }
+        options.print_assets.extend(self.print_assets.clone());
+    }
+}
+
+fn search_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(FILE_NAME)];
+
+    if let Some(exe_dir) = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        paths.push(exe_dir.join(FILE_NAME));
+    }
+
+    let config_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+    };
+
+    if let Some(config_dir) = config_dir {
+        paths.push(config_dir.join("bls2brs").join(FILE_NAME));
+    }
+
+    paths
+}
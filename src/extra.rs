@@ -0,0 +1,62 @@
+//! Reading the `+-` lines that follow a brick in a bls file.
+
+/// Iterates over the extra lines of a brick with `kind`, yielding the rest of each line.
+fn lines<'a>(from: &'a bl_save::Brick, kind: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    from.unknown_extra.iter().filter_map(move |line| {
+        let line = line.strip_prefix("+-").unwrap_or(line);
+        let rest = line.strip_prefix(kind)?;
+        if rest.is_empty() {
+            Some(rest)
+        } else {
+            // Events separate their fields with tabs
+            rest.strip_prefix([' ', '\t'])
+        }
+    })
+}
+
+/// The BL_ID of the player who planted the brick.
+pub fn owner_id(from: &bl_save::Brick) -> Option<u32> {
+    lines(from, "OWNER").next()?.trim().parse().ok()
+}
+
+/// The name given to the brick with the wrench, used by events to refer to it.
+pub fn name(from: &bl_save::Brick) -> Option<&str> {
+    let name = lines(from, "NTOBJECTNAME").next()?.trim();
+    // Blockland stores names with a leading underscore
+    let name = name.strip_prefix('_').unwrap_or(name);
+    (!name.is_empty()).then_some(name)
+}
+
+/// Whether the brick has events, which brs files can't hold.
+pub fn has_events(from: &bl_save::Brick) -> bool {
+    lines(from, "EVENT").next().is_some()
+}
+
+/// Names of the bricks the brick's events teleport players to. Event lines hold the
+/// input, target and output event, then its parameters, and teleport events take the
+/// destination brick's name as their first parameter.
+pub fn teleport_targets(from: &bl_save::Brick) -> Vec<String> {
+    let mut targets = Vec::new();
+    for event in lines(from, "EVENT") {
+        let fields: Vec<&str> = event.split('\t').collect();
+        let is_teleport = fields
+            .get(6)
+            .is_some_and(|output| output.to_ascii_lowercase().contains("teleport"));
+        let target = fields.get(7).map(|target| target.trim());
+        if let Some(target) = target.filter(|target| is_teleport && !target.is_empty()) {
+            let target = target.strip_prefix('_').unwrap_or(target).to_string();
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    targets
+}
+
+/// The music played by the brick, from the name of its audio profile.
+pub fn music(from: &bl_save::Brick) -> Option<String> {
+    let profile = lines(from, "AUDIOEMITTER").next()?;
+    let profile = profile.split('"').next()?.trim();
+    let track = profile.strip_prefix("musicData_").unwrap_or(profile);
+    (!track.is_empty()).then(|| track.replace('_', " "))
+}
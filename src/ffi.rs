@@ -0,0 +1,94 @@
+//! C API, built with `--features ffi`. See `include/bls2brs.h`.
+
+use crate::{convert_io, ConvertOptions};
+use std::{
+    ffi::CStr,
+    fs::{self, File},
+    io::BufReader,
+    os::raw::{c_char, c_int},
+    panic,
+};
+
+pub const BLS2BRS_OK: c_int = 0;
+pub const BLS2BRS_INVALID_ARGUMENT: c_int = 1;
+pub const BLS2BRS_READ_FAILED: c_int = 2;
+pub const BLS2BRS_CONVERT_FAILED: c_int = 3;
+pub const BLS2BRS_WRITE_FAILED: c_int = 4;
+/// The converter panicked. Panics are caught so they don't unwind into C.
+pub const BLS2BRS_INTERNAL_ERROR: c_int = 5;
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Bls2brsReport {
+    pub count_success: usize,
+    pub count_failure: usize,
+    pub count_unknown_ui_names: usize,
+    pub count_out_of_range: usize,
+    pub count_duplicates: usize,
+    pub brick_count: usize,
+}
+
+/// Converts the bls file at `in_path` to a brs file at `out_path`.
+///
+/// Returns `BLS2BRS_OK` on success or one of the other `BLS2BRS_*` codes. `out` is
+/// filled in on success if it isn't null. `out_path` is left alone unless the conversion
+/// succeeds.
+///
+/// # Safety
+///
+/// `in_path` and `out_path` must be null or valid nul-terminated UTF-8 strings, and
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bls2brs_convert(
+    in_path: *const c_char,
+    out_path: *const c_char,
+    out: *mut Bls2brsReport,
+) -> c_int {
+    panic::catch_unwind(|| convert(in_path, out_path, out)).unwrap_or(BLS2BRS_INTERNAL_ERROR)
+}
+
+unsafe fn convert(
+    in_path: *const c_char,
+    out_path: *const c_char,
+    out: *mut Bls2brsReport,
+) -> c_int {
+    if in_path.is_null() || out_path.is_null() {
+        return BLS2BRS_INVALID_ARGUMENT;
+    }
+
+    let (in_path, out_path) = match (
+        CStr::from_ptr(in_path).to_str(),
+        CStr::from_ptr(out_path).to_str(),
+    ) {
+        (Ok(in_path), Ok(out_path)) => (in_path, out_path),
+        _ => return BLS2BRS_INVALID_ARGUMENT,
+    };
+
+    let input = match File::open(in_path) {
+        Ok(file) => BufReader::new(file),
+        Err(_) => return BLS2BRS_READ_FAILED,
+    };
+
+    let mut brs = Vec::new();
+    let converted = match convert_io(input, &mut brs, &ConvertOptions::default()) {
+        Ok(converted) => converted,
+        Err(_) => return BLS2BRS_CONVERT_FAILED,
+    };
+
+    if fs::write(out_path, &brs).is_err() {
+        return BLS2BRS_WRITE_FAILED;
+    }
+
+    if !out.is_null() {
+        *out = Bls2brsReport {
+            count_success: converted.count_success,
+            count_failure: converted.count_failure,
+            count_unknown_ui_names: converted.unknown_ui_names.len(),
+            count_out_of_range: converted.count_out_of_range,
+            count_duplicates: converted.count_duplicates,
+            brick_count: converted.write_data.bricks.len(),
+        };
+    }
+
+    BLS2BRS_OK
+}
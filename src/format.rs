@@ -0,0 +1,127 @@
+//! Recognizing the layout of a bls file before reading it, so that saves `bl_save` can't
+//! read fail with an explanation.
+//!
+//! Blockland saves have no version number. They start with a fixed header line and the
+//! description, followed by the color palette, and, in the saves `bl_save` reads, a
+//! `Linecount` line with the number of bricks.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt,
+    io::{self, prelude::*},
+};
+
+/// The start of the first line of every bls file.
+pub const HEADER: &str = "This is a Blockland save file.";
+
+/// Number of palette colors in the saves `bl_save` reads.
+pub const COLOR_COUNT: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFormat {
+    /// Colors in the palette.
+    pub color_count: usize,
+    /// Whether the palette is followed by a `Linecount` line.
+    pub has_linecount: bool,
+}
+
+impl SaveFormat {
+    /// Whether `bl_save` can read saves of this layout.
+    pub fn is_supported(&self) -> bool {
+        self.color_count == COLOR_COUNT && self.has_linecount
+    }
+}
+
+impl fmt::Display for SaveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} colors", self.color_count)?;
+        if !self.has_linecount {
+            write!(f, " without a Linecount line")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the layout of a bls file from its start, without consuming it. Fails if it isn't
+/// a bls file, or is one `bl_save` can't read. `None` if the start of the file is too long
+/// to look at, such as with a very long description.
+pub fn detect(r: &mut impl BufRead) -> io::Result<Option<SaveFormat>> {
+    let buffer = String::from_utf8_lossy(r.fill_buf()?).into_owned();
+    // The last line may be cut off by the end of the buffer
+    let complete_lines = match buffer.rfind('\n') {
+        Some(end) => &buffer[..end],
+        None => "",
+    };
+    let mut lines = complete_lines.lines();
+
+    if !lines.next().is_some_and(|line| line.starts_with(HEADER)) {
+        return Err(invalid_data(String::from("not a Blockland save file")));
+    }
+
+    let description_lines: usize = match lines.next().map(|line| line.trim().parse()) {
+        Some(Ok(count)) => count,
+        Some(Err(_)) => {
+            return Err(invalid_data(String::from(
+                "invalid description line count in header",
+            )))
+        }
+        None => return Ok(None),
+    };
+
+    let mut lines = lines.skip(description_lines).peekable();
+
+    let mut color_count = 0;
+    while lines.next_if(|line| is_color_line(line)).is_some() {
+        color_count += 1;
+    }
+
+    let has_linecount = match lines.next() {
+        Some(line) => line.starts_with("Linecount "),
+        None => return Ok(None),
+    };
+
+    let format = SaveFormat {
+        color_count,
+        has_linecount,
+    };
+
+    if !format.is_supported() {
+        return Err(invalid_data(format!(
+            "unsupported save layout with {}, only saves with {} colors and a Linecount \
+             line can be read",
+            format, COLOR_COUNT
+        )));
+    }
+
+    Ok(Some(format))
+}
+
+/// A hash of everything in a bls file after its description, ignoring line endings, so
+/// copies of a save that only differ in their description or were edited on another
+/// system match. Doesn't check that `r` is a bls file.
+pub fn fingerprint(r: impl BufRead) -> io::Result<String> {
+    let mut lines = r.split(b'\n');
+    lines.next().transpose()?;
+    let description_lines = match lines.next().transpose()? {
+        Some(line) => String::from_utf8_lossy(&line).trim().parse().unwrap_or(0),
+        None => 0,
+    };
+
+    let mut hasher = Sha256::new();
+    for line in lines.skip(description_lines) {
+        let line = line?;
+        hasher.update(line.strip_suffix(b"\r").unwrap_or(&line));
+        hasher.update(b"\n");
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn is_color_line(line: &str) -> bool {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    fields.len() == 4 && fields.iter().all(|field| field.parse::<f32>().is_ok())
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,57 @@
+//! The rotation and axis math the converter uses to place bricks, for tools that
+//! post-process converted positions.
+//!
+//! Brickadia positions are in units, 10 to a stud and 4 to a plate. Blockland positions
+//! are in Torque units, two studs to a unit, with X and Y swapped.
+
+use std::ops::Neg;
+
+/// Output units per Blockland position unit.
+pub const UNITS_PER_BLOCKLAND_UNIT: f32 = 20.0;
+
+/// Converts a Blockland brick position to a Brickadia position in units, swapping X and Y.
+pub fn brickadia_position((x, y, z): (f32, f32, f32)) -> (i64, i64, i64) {
+    (
+        (y * UNITS_PER_BLOCKLAND_UNIT) as i64,
+        (x * UNITS_PER_BLOCKLAND_UNIT) as i64,
+        (z * UNITS_PER_BLOCKLAND_UNIT) as i64,
+    )
+}
+
+/// Turns an XY offset by `angle` quarter turns, as Blockland turns a brick with that
+/// angle. Angles of 4 and up wrap around.
+pub fn rotate_offset(mut offset: (i32, i32), angle: u8) -> (i32, i32) {
+    for _ in 0..angle % 4 {
+        offset = rotate_90_2d(offset);
+    }
+    offset
+}
+
+/// Turns a point a quarter turn, from X+ toward Y+.
+pub fn rotate_90_2d<X, Y: Neg>((x, y): (X, Y)) -> (<Y as Neg>::Output, X) {
+    (-y, x)
+}
+
+/// Whether a direction points sideways rather than up or down.
+pub fn is_horizontal(direction: brs::Direction) -> bool {
+    !matches!(
+        direction,
+        brs::Direction::ZPositive | brs::Direction::ZNegative
+    )
+}
+
+/// Turns a horizontal direction the same way as `rotate_offset`. Up and down are kept.
+pub fn rotate_direction(mut direction: brs::Direction, angle: u8) -> brs::Direction {
+    use brs::Direction::*;
+
+    for _ in 0..angle % 4 {
+        direction = match direction {
+            XPositive => YPositive,
+            YPositive => XNegative,
+            XNegative => YNegative,
+            YNegative => XPositive,
+            vertical => vertical,
+        };
+    }
+    direction
+}
@@ -0,0 +1,163 @@
+//! Exporting converted saves as glTF binaries (`.glb`).
+
+use crate::{passes::brick_bounds, resolve_color};
+use std::io::{self, Write};
+
+/// Brickadia units are centimeters, glTF uses meters.
+const SCALE: f32 = 0.01;
+
+// Unit cube faces as (normal, corners), corners counter-clockwise seen from outside
+#[rustfmt::skip]
+const FACES: [([f32; 3], [[f32; 3]; 4]); 6] = [
+    ([1.0, 0.0, 0.0], [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 1.0, 1.0], [1.0, 0.0, 1.0]]),
+    ([-1.0, 0.0, 0.0], [[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 1.0], [0.0, 1.0, 0.0]]),
+    ([0.0, 1.0, 0.0], [[0.0, 1.0, 0.0], [0.0, 1.0, 1.0], [1.0, 1.0, 1.0], [1.0, 1.0, 0.0]]),
+    ([0.0, -1.0, 0.0], [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 0.0, 1.0], [0.0, 0.0, 1.0]]),
+    ([0.0, 0.0, 1.0], [[0.0, 0.0, 1.0], [1.0, 0.0, 1.0], [1.0, 1.0, 1.0], [0.0, 1.0, 1.0]]),
+    ([0.0, 0.0, -1.0], [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0], [1.0, 0.0, 0.0]]),
+];
+
+/// Writes `data` as a binary glTF with one box per brick, colored with vertex colors.
+///
+/// Bricks are drawn as their bounding boxes, so wedges and round bricks appear as
+/// boxes. This is meant for previews and archives, not a faithful render.
+pub fn write_glb(mut w: impl Write, data: &brs::WriteData) -> io::Result<()> {
+    let vertex_count = data.bricks.len() * 24;
+    let mut positions = Vec::with_capacity(vertex_count * 3);
+    let mut normals = Vec::with_capacity(vertex_count * 3);
+    let mut colors = Vec::with_capacity(vertex_count * 4);
+    let mut indices: Vec<u32> = Vec::with_capacity(data.bricks.len() * 36);
+
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+
+    for brick in &data.bricks {
+        let (lo, hi) = brick_bounds(brick);
+
+        // Unreal is left-handed and Z-up, glTF is right-handed and Y-up
+        let lo = [
+            lo[0] as f32 * SCALE,
+            lo[2] as f32 * SCALE,
+            lo[1] as f32 * SCALE,
+        ];
+        let hi = [
+            hi[0] as f32 * SCALE,
+            hi[2] as f32 * SCALE,
+            hi[1] as f32 * SCALE,
+        ];
+
+        for axis in 0..3 {
+            min[axis] = min[axis].min(lo[axis]);
+            max[axis] = max[axis].max(hi[axis]);
+        }
+
+        let color = resolve_color(data, &brick.color);
+        let rgba = [
+            f32::from(color.r()) / 255.0,
+            f32::from(color.g()) / 255.0,
+            f32::from(color.b()) / 255.0,
+            f32::from(color.a()) / 255.0,
+        ];
+
+        for (normal, corners) in &FACES {
+            let first = (positions.len() / 3) as u32;
+
+            for corner in corners {
+                for axis in 0..3 {
+                    positions.push(lo[axis] + (hi[axis] - lo[axis]) * corner[axis]);
+                }
+                normals.extend_from_slice(normal);
+                colors.extend_from_slice(&rgba);
+            }
+
+            indices.extend_from_slice(&[first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+    }
+
+    if data.bricks.is_empty() {
+        min = [0.0; 3];
+        max = [0.0; 3];
+    }
+
+    let mut bin = Vec::with_capacity(
+        (positions.len() + normals.len() + colors.len()) * 4 + indices.len() * 4,
+    );
+    let mut views = Vec::new();
+
+    for floats in [&positions, &normals, &colors] {
+        views.push((bin.len(), floats.len() * 4, 34962));
+        for f in floats {
+            bin.extend_from_slice(&f.to_le_bytes());
+        }
+    }
+
+    views.push((bin.len(), indices.len() * 4, 34963));
+    for i in &indices {
+        bin.extend_from_slice(&i.to_le_bytes());
+    }
+
+    let buffer_views = views
+        .iter()
+        .map(|(offset, length, target)| {
+            format!(
+                r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+                offset, length, target
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let json = format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"bls2brs {}"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+            r#""materials":[{{"pbrMetallicRoughness":{{"metallicFactor":0}}}}],"#,
+            r#""meshes":[{{"primitives":[{{"attributes":{{"POSITION":0,"NORMAL":1,"COLOR_0":2}},"indices":3,"material":0}}]}}],"#,
+            r#""buffers":[{{"byteLength":{}}}],"#,
+            r#""bufferViews":[{}],"#,
+            r#""accessors":["#,
+            r#"{{"bufferView":0,"componentType":5126,"count":{},"type":"VEC3","min":[{},{},{}],"max":[{},{},{}]}},"#,
+            r#"{{"bufferView":1,"componentType":5126,"count":{},"type":"VEC3"}},"#,
+            r#"{{"bufferView":2,"componentType":5126,"count":{},"type":"VEC4"}},"#,
+            r#"{{"bufferView":3,"componentType":5125,"count":{},"type":"SCALAR"}}"#,
+            r#"]}}"#,
+        ),
+        env!("CARGO_PKG_VERSION"),
+        bin.len(),
+        buffer_views,
+        vertex_count,
+        min[0],
+        min[1],
+        min[2],
+        max[0],
+        max[1],
+        max[2],
+        vertex_count,
+        vertex_count,
+        indices.len(),
+    );
+
+    let mut json = json.into_bytes();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    while bin.len() % 4 != 0 {
+        bin.push(0);
+    }
+
+    let total = 12 + 8 + json.len() + 8 + bin.len();
+
+    w.write_all(b"glTF")?;
+    w.write_all(&2u32.to_le_bytes())?;
+    w.write_all(&(total as u32).to_le_bytes())?;
+
+    w.write_all(&(json.len() as u32).to_le_bytes())?;
+    w.write_all(b"JSON")?;
+    w.write_all(&json)?;
+
+    w.write_all(&(bin.len() as u32).to_le_bytes())?;
+    w.write_all(b"BIN\0")?;
+    w.write_all(&bin)?;
+
+    w.flush()
+}
@@ -0,0 +1,288 @@
+//! A JSON form of converted saves, for post-processing them with other tools.
+//!
+//! The document mirrors `brs::WriteData`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "map": "Unknown",
+//!   "author": { "id": "00000000-0000-0000-0000-000000000000", "name": "Unknown" },
+//!   "description": "",
+//!   "save_time": "2020-01-01T00:00:00+00:00",
+//!   "mods": [],
+//!   "brick_assets": ["PB_DefaultBrick"],
+//!   "colors": [[255, 255, 255, 255]],
+//!   "materials": ["BMC_Plastic"],
+//!   "brick_owners": [{ "id": "...", "name": "PUBLIC", "brick_count": 1 }],
+//!   "bricks": [
+//!     {
+//!       "asset_name_index": 0,
+//!       "size": [5, 5, 6],
+//!       "position": [0, 0, 6],
+//!       "direction": "ZPositive",
+//!       "rotation": 0,
+//!       "collision": true,
+//!       "visibility": true,
+//!       "material_index": 0,
+//!       "color": 0,
+//!       "owner_index": 0
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `brick_count` is written for information only and ignored when reading.
+//! `rotation` is in quarter turns (0-3). `color` is either an index into `colors` or an
+//! `[r, g, b, a]` array for a custom color. Colors are in Brickadia's linear color space.
+
+use brs::{chrono::prelude::*, uuid::Uuid};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryInto,
+    io::{self, prelude::*},
+};
+
+/// The current `version` of the document.
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveJson {
+    pub version: u32,
+    pub map: String,
+    pub author: UserJson,
+    pub description: String,
+    pub save_time: String,
+    pub mods: Vec<String>,
+    pub brick_assets: Vec<String>,
+    pub colors: Vec<[u8; 4]>,
+    pub materials: Vec<String>,
+    pub brick_owners: Vec<UserJson>,
+    pub bricks: Vec<BrickJson>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserJson {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brick_count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrickJson {
+    pub asset_name_index: u32,
+    pub size: [u32; 3],
+    pub position: [i32; 3],
+    pub direction: String,
+    pub rotation: u8,
+    pub collision: bool,
+    pub visibility: bool,
+    pub material_index: u32,
+    pub color: ColorJson,
+    pub owner_index: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColorJson {
+    Index(u32),
+    Custom([u8; 4]),
+}
+
+impl SaveJson {
+    pub fn from_write_data(data: &brs::WriteData) -> Self {
+        Self {
+            version: VERSION,
+            map: data.map.clone(),
+            author: UserJson::from_user(&data.author),
+            description: data.description.clone(),
+            save_time: data.save_time.to_rfc3339(),
+            mods: data.mods.clone(),
+            brick_assets: data.brick_assets.clone(),
+            colors: data.colors.iter().map(color_to_json).collect(),
+            materials: data.materials.clone(),
+            brick_owners: data
+                .brick_owners
+                .iter()
+                .zip(crate::owner_brick_counts(data))
+                .map(|(user, count)| UserJson {
+                    brick_count: Some(count),
+                    ..UserJson::from_user(user)
+                })
+                .collect(),
+            bricks: data.bricks.iter().map(BrickJson::from_brick).collect(),
+        }
+    }
+
+    pub fn into_write_data(self) -> Result<brs::WriteData, String> {
+        if self.version != VERSION {
+            return Err(format!("unsupported version {}", self.version));
+        }
+
+        let save_time = DateTime::parse_from_rfc3339(&self.save_time)
+            .map_err(|e| format!("invalid save_time: {}", e))?
+            .with_timezone(&Utc);
+
+        Ok(brs::WriteData {
+            map: self.map,
+            author: self.author.into_user()?,
+            description: self.description,
+            save_time,
+            mods: self.mods,
+            brick_assets: self.brick_assets,
+            colors: self.colors.iter().map(color_from_json).collect(),
+            materials: self.materials,
+            brick_owners: self
+                .brick_owners
+                .into_iter()
+                .map(UserJson::into_user)
+                .collect::<Result<_, _>>()?,
+            bricks: self
+                .bricks
+                .into_iter()
+                .map(BrickJson::into_brick)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl UserJson {
+    pub(crate) fn from_user(user: &brs::User) -> Self {
+        Self {
+            id: user.id.to_string(),
+            name: user.name.clone(),
+            brick_count: None,
+        }
+    }
+
+    pub(crate) fn into_user(self) -> Result<brs::User, String> {
+        let id = Uuid::parse_str(&self.id)
+            .map_err(|e| format!("invalid id for user {:?}: {}", self.name, e))?;
+        Ok(brs::User {
+            id,
+            name: self.name,
+        })
+    }
+}
+
+impl BrickJson {
+    pub(crate) fn from_brick(brick: &brs::Brick) -> Self {
+        let (sx, sy, sz) = brick.size;
+        let (x, y, z) = brick.position;
+
+        Self {
+            asset_name_index: brick.asset_name_index,
+            size: [sx, sy, sz],
+            position: [x, y, z],
+            direction: format!("{:?}", brick.direction),
+            rotation: brick.rotation as u8,
+            collision: brick.collision,
+            visibility: brick.visibility,
+            material_index: brick.material_index,
+            color: match &brick.color {
+                brs::ColorMode::Set(index) => ColorJson::Index(*index),
+                brs::ColorMode::Custom(color) => ColorJson::Custom(color_to_json(color)),
+            },
+            owner_index: brick.owner_index,
+        }
+    }
+
+    pub(crate) fn into_brick(self) -> Result<brs::Brick, String> {
+        let direction = parse_direction(&self.direction)?;
+
+        let rotation = self
+            .rotation
+            .try_into()
+            .map_err(|_| format!("invalid rotation {}", self.rotation))?;
+
+        let [sx, sy, sz] = self.size;
+        let [x, y, z] = self.position;
+
+        Ok(brs::Brick {
+            asset_name_index: self.asset_name_index,
+            size: (sx, sy, sz),
+            position: (x, y, z),
+            direction,
+            rotation,
+            collision: self.collision,
+            visibility: self.visibility,
+            material_index: self.material_index,
+            color: match self.color {
+                ColorJson::Index(index) => brs::ColorMode::Set(index),
+                ColorJson::Custom(color) => brs::ColorMode::Custom(color_from_json(&color)),
+            },
+            owner_index: self.owner_index,
+        })
+    }
+}
+
+/// Parses a direction written as in `BrickJson::direction`.
+pub(crate) fn parse_direction(direction: &str) -> Result<brs::Direction, String> {
+    use brs::Direction::*;
+
+    Ok(match direction {
+        "XPositive" => XPositive,
+        "XNegative" => XNegative,
+        "YPositive" => YPositive,
+        "YNegative" => YNegative,
+        "ZPositive" => ZPositive,
+        "ZNegative" => ZNegative,
+        other => return Err(format!("invalid direction {:?}", other)),
+    })
+}
+
+pub(crate) fn color_to_json(color: &brs::Color) -> [u8; 4] {
+    [color.r(), color.g(), color.b(), color.a()]
+}
+
+pub(crate) fn color_from_json(&[r, g, b, a]: &[u8; 4]) -> brs::Color {
+    brs::Color::from_rgba(r, g, b, a)
+}
+
+/// Writes `data` as a pretty-printed JSON document.
+pub fn write_json(w: impl Write, data: &brs::WriteData) -> io::Result<()> {
+    serde_json::to_writer_pretty(w, &SaveJson::from_write_data(data)).map_err(io::Error::from)
+}
+
+/// Reads a JSON document back into save data that can be written with `brs::write_save`.
+pub fn read_json(r: impl Read) -> io::Result<brs::WriteData> {
+    let save: SaveJson = serde_json::from_reader(r).map_err(io::Error::from)?;
+    save.into_write_data()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// `#[serde(with)]` modules for the `brs` types in `ConvertReport`, written as in this
+/// document.
+pub(crate) mod report {
+    use super::SaveJson;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub mod write_data {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(data: &brs::WriteData, s: S) -> Result<S::Ok, S::Error> {
+            SaveJson::from_write_data(data).serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<brs::WriteData, D::Error> {
+            SaveJson::deserialize(d)?
+                .into_write_data()
+                .map_err(D::Error::custom)
+        }
+    }
+
+    pub mod direction {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            direction: &brs::Direction,
+            s: S,
+        ) -> Result<S::Ok, S::Error> {
+            format!("{:?}", direction).serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<brs::Direction, D::Error> {
+            super::super::parse_direction(&String::deserialize(d)?).map_err(D::Error::custom)
+        }
+    }
+}
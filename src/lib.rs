@@ -1,121 +1,1523 @@
 use brs::{chrono::prelude::*, uuid::Uuid};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     convert::TryInto,
+    fmt,
     io::{self, prelude::*},
-    ops::Neg,
+    sync::Arc,
 };
 
 pub use bl_save;
 pub use brs;
 
+pub mod archive;
+pub mod assets;
+pub mod checkpoint;
+pub mod color;
+pub mod colorset;
+pub mod config;
+mod extra;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod geometry;
+pub mod gltf;
+pub mod json;
+pub mod lint;
+pub mod owners;
+pub mod pipeline;
+pub mod preview;
+pub mod serve;
+pub mod stats;
 mod types;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 #[macro_use]
 mod misc;
 mod mappings;
+mod passes;
+mod warning;
 
-use mappings::{BRICK_MAP_LITERAL, BRICK_MAP_REGEX};
+pub use passes::Overlap;
+pub use types::PrintFace;
+pub use warning::{ConvertWarning, Severity, PALETTE_LIMIT};
+
+use checkpoint::Checkpoint;
+use color::ColorMapper;
+use format::SaveFormat;
+use geometry::{brickadia_position, is_horizontal, rotate_direction, rotate_offset};
+use mappings::{
+    BRICK_MAP_APPROXIMATE, BRICK_MAP_EXACT, BRICK_MAP_REGEX, BRICK_MAP_REGEX_SET, PLACEHOLDER_SIZES,
+};
+use owners::OwnerMap;
+use pipeline::Pipeline;
 use types::{BrickDesc, BrickMapping};
 
-// Keep this in sync. Would be nice to just determine the indices at compile time.
-const FIXED_MATERIAL_TABLE: &[&str] = &["BMC_Plastic", "BMC_Glow", "BMC_Metallic"];
-const BMC_PLASTIC: usize = 0;
-const BMC_GLOW: usize = 1;
-const BMC_METALLIC: usize = 2;
+/// The default `ConvertOptions::materials`.
+pub const DEFAULT_MATERIALS: &[&str] = &[BMC_PLASTIC, BMC_GLOW, BMC_METALLIC, BMC_GLASS];
+const BMC_PLASTIC: &str = "BMC_Plastic";
+const BMC_GLOW: &str = "BMC_Glow";
+const BMC_METALLIC: &str = "BMC_Metallic";
+const BMC_GLASS: &str = "BMC_Glass";
 
+// Owner index 0 is PUBLIC, anything else is a 1-based index into `brick_owners`.
 const BRICK_OWNER: usize = 0;
+/// Parse errors in a row after which `ConvertOptions::lenient` gives up on the rest of
+/// the file.
+const MAX_CONSECUTIVE_PARSE_ERRORS: usize = 1000;
+/// Unknown brick names listed by `ConvertReport::summary`, the most common first.
+const SUMMARY_UNKNOWN_NAMES: usize = 10;
+
+/// Unreal's `HALF_WORLD_MAX`; bricks further out than this are unusable in Brickadia.
+pub const DEFAULT_COORDINATE_LIMIT: i32 = 1_048_576;
+
+pub struct ConvertOptions {
+    /// Written as the save's author.
+    pub author: brs::User,
+    /// Written as the save's map.
+    pub map: String,
+    /// Written as the save's material table, in this order. Materials that bricks use but
+    /// that aren't listed are added after these.
+    pub materials: Vec<String>,
+    /// Bricks whose color has an alpha below this (0-1) use the glass material.
+    /// Set to 0 to never use glass.
+    pub glass_alpha_threshold: f32,
+    pub color_space: ColorSpaceMode,
+    /// Maps the save's colors instead of `color_space` and `curated_colors`, see
+    /// `color::ColorMapper`.
+    pub color_mapper: Option<Box<dyn ColorMapper + Send + Sync>>,
+    /// If the save uses Blockland's stock colorset, write hand-picked Brickadia colors for
+    /// its main colors instead of converting them. See `ConvertReport::stock_colorset`.
+    /// Off by default: only the first nine stock colors have curated values, and any
+    /// colorset that starts with them is taken for the stock one.
+    pub curated_colors: bool,
+    /// How to write bricks that blink, swirl, cycle through colors or flicker.
+    pub animated_fx: AnimatedFx,
+    /// Largest absolute output coordinate on any axis.
+    pub coordinate_limit: i32,
+    /// What to do with bricks beyond `coordinate_limit`.
+    pub out_of_range: OutOfRangePolicy,
+    pub scale: Scale,
+    /// Replace assets this Brickadia version doesn't have, see
+    /// `ConvertReport::substituted_assets`.
+    pub target: Target,
+    /// Remove exact duplicate bricks, as left behind by Blockland's ghost planting bugs.
+    pub dedup: bool,
+    /// Remove large flat baseplates on the lowest level of the build, usually a ground
+    /// floor that Brickadia doesn't need.
+    pub strip_baseplates: bool,
+    /// Put baseplates under the whole build, for builds that stood on Blockland's
+    /// terrain and would otherwise float.
+    pub add_baseplate: bool,
+    /// Merge cubes lying on a shared grid, as in Blockland cube terrain, into larger
+    /// bricks. See `ConvertReport::count_terrain_merged`.
+    pub merge_terrain: bool,
+    /// Passes to run after the ones above, in order.
+    pub pipeline: Pipeline,
+    /// Find output bricks that intersect each other, see `ConvertReport::overlaps`.
+    pub detect_overlaps: bool,
+    /// Move bricks by up to this many units on each axis to line them up with the stud
+    /// and plate grid, closing seams left by rounding. See `ConvertReport::count_snapped`.
+    pub grid_snap: Option<u32>,
+    /// Who owns the bricks planted by each Blockland player. Public by default.
+    pub owners: OwnerMap,
+    /// Owns every converted brick instead, regardless of `owners`.
+    pub owner: Option<brs::User>,
+    /// If not empty, only bricks planted by these BL_IDs are converted.
+    pub only_owners: HashSet<u32>,
+    /// Leave out bricks that aren't rendered, such as hidden event helpers.
+    pub skip_invisible: bool,
+    /// Leave out bricks without collision.
+    pub skip_noncolliding: bool,
+    /// Leave out water bricks, which are otherwise written as translucent blue bricks
+    /// without collision, and bricks with the water shape effect, which are otherwise
+    /// written translucent in their own color.
+    pub skip_water: bool,
+    /// Write brightly colored bricks of the right footprint in place of interactive bricks
+    /// like vehicle spawns, see `ConvertReport::placeholders`.
+    pub placeholders: bool,
+    /// Write a brick roughly the size of each brick that can't be mapped, in the same color
+    /// as `placeholders`, instead of leaving it out.
+    pub placeholder_unknown: bool,
+    /// Record where each output brick came from, see `ConvertReport::provenance`.
+    pub provenance: bool,
+    /// Compare each decomposition with the footprint in its brick's name, see
+    /// `ConvertReport::geometry_issues`. For working on mappings.
+    pub check_geometry: bool,
+    /// Skip brick lines that can't be read instead of failing, to salvage damaged saves,
+    /// see `ConvertReport::count_parse_errors`.
+    pub lenient: bool,
+    /// Fail once the converted bricks would take more than about this many bytes of
+    /// memory, instead of running out. Also limits how much is allocated up front.
+    pub memory_limit: Option<u64>,
+    /// Save progress every so many bricks and pick up from there if it was saved before.
+    /// Call `Checkpoint::clear` once the converted save is written. Can't be used with
+    /// `provenance`.
+    pub checkpoint: Option<Checkpoint>,
+    /// Fail the conversion if any brick can't be mapped or uses an asset that isn't in
+    /// `known_assets`, instead of leaving it out or writing it anyway.
+    pub strict: bool,
+    /// Brick assets Brickadia has, see `ConvertReport::unknown_assets`. Empty to not check.
+    pub known_assets: Vec<String>,
+    /// Assets to use for printed plates by print name, for prints that have a matching
+    /// Brickadia tile or decal. Printed bricks such as signs get the asset as a decal on
+    /// their front face. Other printed plates become smooth tiles and are listed in
+    /// `ConvertReport::dropped_prints`, as are bricks whose print has no asset.
+    pub print_assets: HashMap<String, String>,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            author: brs::User {
+                id: Uuid::nil(),
+                name: String::from("Unknown"),
+            },
+            map: String::from("Unknown"),
+            materials: DEFAULT_MATERIALS.iter().map(|s| String::from(*s)).collect(),
+            glass_alpha_threshold: 1.0,
+            color_space: ColorSpaceMode::Srgb,
+            color_mapper: None,
+            curated_colors: false,
+            animated_fx: AnimatedFx::Plastic,
+            coordinate_limit: DEFAULT_COORDINATE_LIMIT,
+            out_of_range: OutOfRangePolicy::Keep,
+            scale: Scale::Normal,
+            target: Target::Latest,
+            dedup: false,
+            strip_baseplates: false,
+            add_baseplate: false,
+            merge_terrain: false,
+            pipeline: Pipeline::default(),
+            detect_overlaps: false,
+            grid_snap: None,
+            owners: OwnerMap::default(),
+            owner: None,
+            only_owners: HashSet::new(),
+            skip_invisible: false,
+            skip_noncolliding: false,
+            skip_water: false,
+            placeholders: false,
+            placeholder_unknown: false,
+            provenance: false,
+            check_geometry: false,
+            lenient: false,
+            memory_limit: None,
+            checkpoint: None,
+            strict: false,
+            known_assets: assets::KNOWN_ASSETS
+                .iter()
+                .map(|s| String::from(*s))
+                .collect(),
+            print_assets: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpaceMode {
+    /// sRGB to linear on color channels, alpha is kept linear.
+    Srgb,
+    /// The conversion used by bls2brs 0.3 and earlier, which also gamma expands alpha
+    /// and truncates instead of rounding.
+    Legacy,
+}
 
+/// How to write bricks with an animated Blockland color effect, which Brickadia materials
+/// can't show. See `ConvertReport::animated_fx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnimatedFx {
+    /// As plastic, like bricks without an effect.
+    Plastic,
+    /// With the glow material, so light shows stay bright.
+    Glow,
+    /// In the placeholder color, so they're easy to find and redo in game.
+    Mark,
+}
+
+impl AnimatedFx {
+    /// Parses `plastic`, `glow` or `mark`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "plastic" => Some(Self::Plastic),
+            "glow" => Some(Self::Glow),
+            "mark" => Some(Self::Mark),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRangePolicy {
+    /// Write the brick where it is, only counting it.
+    Keep,
+    /// Move the brick to the nearest in-range position.
+    Clamp,
+    /// Leave the brick out of the output.
+    Drop,
+}
+
+/// The Brickadia version a save is written for. Older versions lack some assets, which
+/// are replaced with the closest ones they have, see `ASSET_SUBSTITUTES`.
+///
+/// The save version is the one the `brs` crate writes, whatever the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Target {
+    A4,
+    A5,
+    Latest,
+}
+
+/// Assets missing from older Brickadia versions, with the newest version that lacks each
+/// and the asset to use instead.
+pub const ASSET_SUBSTITUTES: &[(Target, &str, &str)] = &[
+    (Target::A4, "PB_DefaultRampCrest", "PB_DefaultBrick"),
+    (Target::A4, "PB_DefaultRampCrestCorner", "PB_DefaultBrick"),
+    (Target::A4, "PB_DefaultRampCrestEnd", "PB_DefaultBrick"),
+    (Target::A4, "PB_DefaultSmoothTile", "PB_DefaultTile"),
+];
+
+impl Target {
+    /// Parses `a4`, `a5` or `latest`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "a4" => Some(Self::A4),
+            "a5" => Some(Self::A5),
+            "latest" => Some(Self::Latest),
+            _ => None,
+        }
+    }
+
+    /// The asset to use for `asset` in this version, if it doesn't have it.
+    pub fn substitute(self, asset: &str) -> Option<&'static str> {
+        ASSET_SUBSTITUTES
+            .iter()
+            .find(|(newest_without, from, _)| self <= *newest_without && *from == asset)
+            .map(|(_, _, to)| *to)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    /// One Blockland stud is one Brickadia stud.
+    Normal,
+    /// One Blockland stud is one Brickadia micro brick, a fifth of the size.
+    ///
+    /// Heights are rounded to whole micro bricks, so a plate and a brick both become one
+    /// micro brick tall. This suits ModTer terrain, which scales exactly, better than
+    /// detailed builds. Only box-shaped procedural assets scale exactly. Other procedural
+    /// assets, like ramps and wedges, become micro bricks of their size and count as
+    /// approximate. Non-procedural assets such as `B_Pine_Tree` can't be scaled, so their
+    /// bricks fail with `FailureReason::NotScalable`.
+    Micro,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct ConvertReport {
+    /// Serialized as a `json` document.
+    #[serde(with = "json::report::write_data")]
     pub write_data: brs::WriteData,
+    /// The bls file's palette, as RGBA from 0 to 1. The first entries of
+    /// `write_data.colors` are these colors converted.
+    pub source_colors: Vec<(f32, f32, f32, f32)>,
     pub unknown_ui_names: HashMap<String, usize>,
+    /// Source bricks by ui name that were converted to something that only resembles them.
+    pub approximate_ui_names: HashMap<String, usize>,
+    /// Names of `unknown_ui_names` that a mapping for their family of bricks matched but
+    /// couldn't convert, such as `3/4h` heights. These need the mapping extended rather
+    /// than a new one.
+    pub rejected_ui_names: HashMap<String, usize>,
     pub count_success: usize,
     pub count_failure: usize,
+    /// Why the bricks of `count_failure` failed, adding up to it.
+    pub failure_reasons: HashMap<FailureReason, usize>,
+    /// Output bricks that were beyond `ConvertOptions::coordinate_limit`.
+    pub count_out_of_range: usize,
+    /// Duplicate bricks removed by `ConvertOptions::dedup`.
+    pub count_duplicates: usize,
+    /// Baseplates removed by `ConvertOptions::strip_baseplates`.
+    pub count_baseplates_stripped: usize,
+    /// Bricks written by `ConvertOptions::add_baseplate`.
+    pub count_baseplate_added: usize,
+    /// Bricks removed by `ConvertOptions::merge_terrain` merging them into others.
+    pub count_terrain_merged: usize,
+    /// Bricks removed by each pass of `ConvertOptions::pipeline`, in order.
+    pub pipeline_removed: Vec<(String, usize)>,
+    /// Bricks moved onto the grid by `ConvertOptions::grid_snap`.
+    pub count_snapped: usize,
+    /// Total distance in units that `ConvertOptions::grid_snap` moved bricks, over all axes.
+    pub snap_adjustment: u64,
+    /// Source bricks left out by `ConvertOptions::only_owners`.
+    pub count_owner_filtered: usize,
+    /// Source bricks left out by `ConvertOptions::skip_invisible`.
+    pub count_invisible_skipped: usize,
+    /// Source bricks left out by `ConvertOptions::skip_noncolliding`.
+    pub count_noncolliding_skipped: usize,
+    /// Source bricks left out by `ConvertOptions::skip_water`.
+    pub count_water_skipped: usize,
+    /// Source bricks with the water shape effect, written with the glass material.
+    pub count_water_fx: usize,
+    /// Source bricks with the water shape effect left out by `ConvertOptions::skip_water`.
+    pub count_water_fx_skipped: usize,
+    /// Source bricks with the undulo shape effect, whose waves Brickadia can't show.
+    pub count_undulo: usize,
+    /// Source bricks by animated color effect (`blink`, `swirl`, `rainbow` or
+    /// `unstable`), written as `animated_fx_mode` says.
+    pub animated_fx: HashMap<String, usize>,
+    /// `ConvertOptions::animated_fx` of the conversion.
+    pub animated_fx_mode: AnimatedFx,
+    /// Intersecting output bricks, if `ConvertOptions::detect_overlaps` is set.
+    pub overlaps: Vec<Overlap>,
+    /// Number of output bricks owned by each entry of `write_data.brick_owners`.
+    pub owner_brick_counts: Vec<usize>,
+    /// Bricks written by mappings for each entry of `write_data.brick_assets`, before
+    /// `ConvertOptions::dedup` and `ConvertOptions::strip_baseplates`.
+    pub asset_counts: Vec<AssetCount>,
+    /// Brick lines that couldn't be read and were skipped by `ConvertOptions::lenient`.
+    /// These also count as failures.
+    pub count_parse_errors: usize,
+    /// Unmapped bricks written as placeholders by `ConvertOptions::placeholder_unknown`.
+    /// These still count as failures.
+    pub count_unknown_placeholders: usize,
+    /// Bricks replaced with placeholders, if `ConvertOptions::placeholders` is set.
+    pub placeholders: Vec<Placeholder>,
+    /// Decompositions whose bounding box doesn't match the footprint of the brick they
+    /// replace, if `ConvertOptions::check_geometry` is set. One per brick name.
+    pub geometry_issues: Vec<GeometryIssue>,
+    /// Bricks whose print was lost because the output asset can't show it.
+    pub dropped_prints: Vec<DroppedPrint>,
+    /// Where the prints of printed bricks would go on the output bricks, for placing
+    /// decals or replacement tiles.
+    pub prints: Vec<PrintPlacement>,
+    /// Bricks that were named for events. brs files can't hold brick names, so they're
+    /// only kept here.
+    pub named_bricks: Vec<NamedBrick>,
+    /// Bricks that played music. brs files can't hold audio components, so the tracks are
+    /// only kept here.
+    pub music_bricks: Vec<MusicBrick>,
+    /// Source bricks with events, which brs files can't hold.
+    pub count_dropped_events: usize,
+    /// Teledoors and teleporters, written as portal placeholders. Their links are events,
+    /// so they're only kept here.
+    pub portals: Vec<Portal>,
+    /// Checkpoints, capture points and spawns, written as markers. Minigame logic can't be
+    /// converted, so these are kept for rebuilding it.
+    pub minigame_bricks: Vec<MinigameBrick>,
+    /// Assets of `write_data.brick_assets` that aren't in `ConvertOptions::known_assets`,
+    /// sorted. Bricks of these load invisible.
+    pub unknown_assets: Vec<String>,
+    /// Assets replaced because `ConvertOptions::target` doesn't have them, with what they
+    /// were replaced with.
+    pub substituted_assets: HashMap<String, String>,
+    /// The layout of the bls file, if it was found with `format::detect`.
+    pub format: Option<SaveFormat>,
+    /// The save uses Blockland's stock colorset, so `ConvertOptions::curated_colors` applied
+    /// if set.
+    pub stock_colorset: bool,
+    /// The main colors of the stock colorset were replaced with curated ones.
+    pub curated_colors: bool,
+    /// Where each brick of `write_data.bricks` came from, if `ConvertOptions::provenance`
+    /// is set.
+    pub provenance: Vec<Provenance>,
+    /// What was lost or changed, most severe first, for frontends to show or filter.
+    pub warnings: Vec<ConvertWarning>,
+}
+
+/// Why a source brick couldn't be converted, see `ConvertReport::failure_reasons`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// No mapping has the brick's ui name.
+    UnknownName,
+    /// A mapping matched the ui name but not this variant of it, see
+    /// `ConvertReport::rejected_ui_names`.
+    UnsupportedVariant,
+    /// The brick's line couldn't be read, see `ConvertOptions::lenient`.
+    ParseError,
+    /// Some of its bricks were beyond `ConvertOptions::coordinate_limit` and left out by
+    /// `OutOfRangePolicy::Drop`.
+    OutOfRange,
+    /// It has a non-procedural asset, which `Scale::Micro` can't scale.
+    NotScalable,
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownName => "unknown name",
+            Self::UnsupportedVariant => "unsupported variant",
+            Self::ParseError => "unreadable",
+            Self::OutOfRange => "out of range",
+            Self::NotScalable => "can't be scaled",
+        })
+    }
+}
+
+/// Where an output brick came from, for debugging mappings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Index of the source brick in the bls file, or `None` for generated bricks.
+    pub source_index: Option<usize>,
+    pub ui_name: String,
+    /// The mapping that produced the brick: `exact` or `approximate` for the literal
+    /// tables, the pattern of a regex mapping, or `placeholder`, `unknown placeholder` or
+    /// `baseplate`.
+    pub rule: Cow<'static, str>,
+    /// Index of the brick within its mapping.
+    pub desc_index: usize,
+}
+
+/// An interactive brick that was written as a placeholder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Placeholder {
+    pub ui_name: String,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+/// A printed brick that was converted without its print.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DroppedPrint {
+    pub ui_name: String,
+    pub print: String,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+/// A decomposition that overhangs or falls short of its source brick, see
+/// `ConvertReport::geometry_issues`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeometryIssue {
+    pub ui_name: String,
+    /// What's wrong on each side, such as `overhangs X+ by 5 units`.
+    pub messages: Vec<String>,
+}
+
+/// The print of a source brick, placed on an output brick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintPlacement {
+    pub print: String,
+    pub face: PrintFace,
+    /// Position of the output brick.
+    pub position: (i32, i32, i32),
+    #[serde(with = "json::report::direction")]
+    pub direction: brs::Direction,
+    /// Rotation of the output brick, which the print follows, in quarter turns.
+    pub rotation: u8,
+    /// The way the printed face points, to use as the `direction_override` of a decal or
+    /// letter brick. For slopes, the horizontal direction the slope faces.
+    #[serde(with = "json::report::direction")]
+    pub facing: brs::Direction,
+}
+
+/// How many output bricks use an asset, see `ConvertReport::asset_counts`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct AssetCount {
+    /// Bricks mapped to a single brick of this asset.
+    pub direct: usize,
+    /// Bricks of this asset that are part of a mapping to several bricks.
+    pub decomposed: usize,
+}
+
+impl AssetCount {
+    pub fn total(&self) -> usize {
+        self.direct + self.decomposed
+    }
+}
+
+/// A brick that was given a name in Blockland.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedBrick {
+    pub name: String,
+    pub ui_name: String,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+/// A teledoor or teleporter, see `ConvertReport::portals`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Portal {
+    pub ui_name: String,
+    /// The brick's name, which other teleporters' events use to send players here.
+    pub name: Option<String>,
+    /// Names of the bricks its events send players to.
+    pub targets: Vec<String>,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+/// What a minigame brick was for, see `ConvertReport::minigame_bricks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MinigameKind {
+    /// Written as a green marker.
+    Checkpoint,
+    /// Written as a red marker.
+    CapturePoint,
+    /// Written as a yellow marker.
+    Spawn,
+}
+
+impl fmt::Display for MinigameKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Checkpoint => "checkpoint",
+            Self::CapturePoint => "capture point",
+            Self::Spawn => "spawn",
+        })
+    }
+}
+
+/// A checkpoint, capture point or spawn of a minigame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinigameBrick {
+    pub kind: MinigameKind,
+    pub ui_name: String,
+    /// The brick's name, which the minigame's events use to refer to it.
+    pub name: Option<String>,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+/// A brick that played music in Blockland.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MusicBrick {
+    pub track: String,
+    pub ui_name: String,
+    /// Position of the original brick, in output coordinates.
+    pub position: (i32, i32, i32),
+}
+
+impl ConvertReport {
+    /// Rough size in bytes of the brs file for `write_data`, without writing it. Bricks
+    /// compress to a few bytes each, so this can be off by half either way.
+    pub fn estimated_size(&self) -> u64 {
+        // Compressed brick data per brick, measured on typical saves
+        const BYTES_PER_BRICK: u64 = 6;
+
+        let data = &self.write_data;
+        let strings = data.map.len()
+            + data.author.name.len()
+            + data.description.len()
+            + data.brick_assets.iter().map(String::len).sum::<usize>()
+            + data.materials.iter().map(String::len).sum::<usize>()
+            + data
+                .brick_owners
+                .iter()
+                .map(|o| 16 + o.name.len())
+                .sum::<usize>();
+
+        (strings + data.colors.len() * 4) as u64 + data.bricks.len() as u64 * BYTES_PER_BRICK
+    }
+
+    /// The counts of this report and its unknown bricks as JSON, for frontends.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "count_success": self.count_success,
+            "count_failure": self.count_failure,
+            "failure_reasons": self.failure_reasons,
+            "count_parse_errors": self.count_parse_errors,
+            "count_out_of_range": self.count_out_of_range,
+            "count_duplicates": self.count_duplicates,
+            "count_owner_filtered": self.count_owner_filtered,
+            "count_invisible_skipped": self.count_invisible_skipped,
+            "count_noncolliding_skipped": self.count_noncolliding_skipped,
+            "count_water_skipped": self.count_water_skipped,
+            "animated_fx": self.animated_fx,
+            "count_water_fx": self.count_water_fx,
+            "count_water_fx_skipped": self.count_water_fx_skipped,
+            "count_undulo": self.count_undulo,
+            "count_dropped_events": self.count_dropped_events,
+            "portal_count": self.portals.len(),
+            "minigame_brick_count": self.minigame_bricks.len(),
+            "brick_count": self.write_data.bricks.len(),
+            "unknown_assets": self.unknown_assets,
+            "unknown_ui_names": self.unknown_ui_names,
+            "rejected_ui_names": self.rejected_ui_names,
+            "format": self.format,
+            "stock_colorset": self.stock_colorset,
+            "curated_colors": self.curated_colors,
+        })
+    }
+
+    /// A plain text summary of what was converted and what was lost, to put in the
+    /// save's description.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "bls2brs {}, {}\n{} of {} bricks converted to {} bricks",
+            env!("CARGO_PKG_VERSION"),
+            self.write_data.save_time.format("%Y-%m-%d %H:%M UTC"),
+            self.count_success,
+            self.count_success + self.count_failure,
+            self.write_data.bricks.len(),
+        );
+
+        if !self.unknown_ui_names.is_empty() {
+            let mut ui_names: Vec<_> = self.unknown_ui_names.iter().collect();
+            ui_names.sort_by(|(an, ac), (bn, bc)| ac.cmp(bc).reverse().then(an.cmp(bn)));
+            summary.push_str("\nUnknown bricks:");
+            for (ui_name, count) in ui_names.iter().take(SUMMARY_UNKNOWN_NAMES) {
+                summary.push_str(&format!("\n  {} x{}", ui_name, count));
+            }
+            if ui_names.len() > SUMMARY_UNKNOWN_NAMES {
+                summary.push_str(&format!(
+                    "\n  and {} more",
+                    ui_names.len() - SUMMARY_UNKNOWN_NAMES
+                ));
+            }
+        }
+
+        if self.count_parse_errors > 0 {
+            summary.push_str(&format!(
+                "\n{} bricks could not be read",
+                self.count_parse_errors
+            ));
+        }
+
+        if !self.placeholders.is_empty() {
+            summary.push_str(&format!(
+                "\n{} interactive bricks replaced with placeholders",
+                self.placeholders.len()
+            ));
+        }
+
+        if !self.music_bricks.is_empty() {
+            summary.push_str(&format!(
+                "\n{} music bricks lost their music",
+                self.music_bricks.len()
+            ));
+        }
+
+        if !self.dropped_prints.is_empty() {
+            summary.push_str(&format!(
+                "\n{} bricks lost their prints",
+                self.dropped_prints.len()
+            ));
+        }
+
+        summary
+    }
+}
+
+/// The report as the command line prints it, one section or count per line. The
+/// alternate form, `{:#}`, adds the bricks per asset.
+impl fmt::Display for ConvertReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.unknown_ui_names.is_empty() {
+            writeln!(f, "Unknown bricks:")?;
+            let mut ui_names: Vec<_> = self.unknown_ui_names.iter().collect();
+            ui_names.sort_by(|(an, ac), (bn, bc)| bc.cmp(ac).then_with(|| an.cmp(bn)));
+            for (ui_name, count) in ui_names {
+                let shown = if ui_name != ui_name.trim() {
+                    format!("{:?}", ui_name)
+                } else {
+                    ui_name.clone()
+                };
+                if self.rejected_ui_names.contains_key(ui_name) {
+                    writeln!(
+                        f,
+                        "  {:<28} {:>4} bricks, unsupported variant",
+                        shown, count
+                    )?;
+                } else {
+                    writeln!(f, "  {:<28} {:>4} bricks", shown, count)?;
+                }
+            }
+        }
+
+        if !self.approximate_ui_names.is_empty() {
+            writeln!(f, "Approximated bricks, worth checking in game:")?;
+            let mut ui_names: Vec<_> = self.approximate_ui_names.iter().collect();
+            ui_names.sort_by(|(an, ac), (bn, bc)| bc.cmp(ac).then_with(|| an.cmp(bn)));
+            for (ui_name, count) in ui_names {
+                writeln!(f, "  {:<28} {:>4} bricks", ui_name, count)?;
+            }
+        }
+
+        if !self.geometry_issues.is_empty() {
+            writeln!(
+                f,
+                "Decompositions that don't match their brick's footprint:"
+            )?;
+            for issue in &self.geometry_issues {
+                writeln!(f, "  {:<28} {}", issue.ui_name, issue.messages.join(", "))?;
+            }
+        }
+
+        if !self.unknown_assets.is_empty() {
+            writeln!(
+                f,
+                "Assets Brickadia doesn't have, their bricks will be invisible:"
+            )?;
+            for asset in &self.unknown_assets {
+                writeln!(f, "  {}", asset)?;
+            }
+        }
+
+        if !self.substituted_assets.is_empty() {
+            writeln!(f, "Assets replaced for --target:")?;
+            let mut assets: Vec<_> = self.substituted_assets.iter().collect();
+            assets.sort();
+            for (asset, substitute) in assets {
+                writeln!(f, "  {:<28} with {}", asset, substitute)?;
+            }
+        }
+
+        if !self.placeholders.is_empty() {
+            writeln!(f, "Placeholders for interactive bricks:")?;
+            for placeholder in &self.placeholders {
+                let (x, y, z) = placeholder.position;
+                writeln!(f, "  {:<28} at ({}, {}, {})", placeholder.ui_name, x, y, z)?;
+            }
+        }
+
+        if !self.dropped_prints.is_empty() {
+            let mut prints: HashMap<&str, usize> = HashMap::new();
+            for dropped in &self.dropped_prints {
+                *prints.entry(dropped.print.as_str()).or_default() += 1;
+            }
+            let mut prints: Vec<_> = prints.into_iter().collect();
+            prints.sort_by(|(an, ac), (bn, bc)| bc.cmp(ac).then_with(|| an.cmp(bn)));
+            writeln!(f, "Prints lost on bricks that can't show them:")?;
+            for (print, count) in prints {
+                writeln!(f, "  {:<28} {:>4} bricks", print, count)?;
+            }
+        }
+
+        if !self.music_bricks.is_empty() {
+            writeln!(f, "Music that can't be played by converted bricks:")?;
+            for music in &self.music_bricks {
+                let (x, y, z) = music.position;
+                writeln!(f, "  {:<28} at ({}, {}, {})", music.track, x, y, z)?;
+            }
+        }
+
+        if !self.portals.is_empty() {
+            writeln!(
+                f,
+                "Teleporters written as portals, their links need redoing:"
+            )?;
+            for portal in &self.portals {
+                let (x, y, z) = portal.position;
+                write!(f, "  {:<28} at ({}, {}, {})", portal.ui_name, x, y, z)?;
+                if let Some(name) = &portal.name {
+                    write!(f, " named {}", name)?;
+                }
+                if !portal.targets.is_empty() {
+                    write!(f, " to {}", portal.targets.join(", "))?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !self.minigame_bricks.is_empty() {
+            writeln!(f, "Minigame bricks written as markers:")?;
+            for brick in &self.minigame_bricks {
+                let (x, y, z) = brick.position;
+                write!(
+                    f,
+                    "  {:<28} at ({}, {}, {}), {}",
+                    brick.ui_name, x, y, z, brick.kind
+                )?;
+                if let Some(name) = &brick.name {
+                    write!(f, " named {}", name)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if self.count_dropped_events > 0 {
+            writeln!(
+                f,
+                "{} bricks lost their events, which brs files can't hold",
+                self.count_dropped_events
+            )?;
+        }
+
+        if f.alternate() {
+            writeln!(f, "Bricks per asset (direct + from decompositions):")?;
+            let mut assets: Vec<_> = self
+                .write_data
+                .brick_assets
+                .iter()
+                .zip(&self.asset_counts)
+                .collect();
+            assets.sort_by(|(_, a), (_, b)| a.total().cmp(&b.total()).reverse());
+            for (asset, count) in assets {
+                writeln!(
+                    f,
+                    "  {:<28} {:>6} = {} + {}",
+                    asset,
+                    count.total(),
+                    count.direct,
+                    count.decomposed
+                )?;
+            }
+        }
+
+        if self.write_data.brick_owners.len() > 1 {
+            writeln!(f, "Bricks per owner:")?;
+            for (user, count) in self
+                .write_data
+                .brick_owners
+                .iter()
+                .zip(&self.owner_brick_counts)
+            {
+                writeln!(f, "  {:<28} {:>4} bricks", user.name, count)?;
+            }
+        }
+
+        if self.count_failure > 0 {
+            writeln!(f, "{} bricks failed to convert", self.count_failure)?;
+            let mut reasons: Vec<_> = self.failure_reasons.iter().collect();
+            reasons.sort();
+            for (reason, count) in reasons {
+                writeln!(f, "  {}: {}", reason, count)?;
+            }
+        }
+
+        let counts = [
+            (
+                self.count_parse_errors,
+                "bricks could not be read and were skipped",
+            ),
+            (
+                self.count_unknown_placeholders,
+                "unknown bricks written as placeholders",
+            ),
+            (
+                self.count_owner_filtered,
+                "bricks skipped because of their owner",
+            ),
+            (self.count_invisible_skipped, "invisible bricks skipped"),
+            (
+                self.count_noncolliding_skipped,
+                "bricks without collision skipped",
+            ),
+            (self.count_water_skipped, "water bricks skipped"),
+        ];
+        for (count, message) in counts {
+            if count > 0 {
+                writeln!(f, "{} {}", count, message)?;
+            }
+        }
+
+        if self.curated_colors {
+            writeln!(
+                f,
+                "Stock Blockland colorset, its main colors were replaced with curated ones"
+            )?;
+        }
+
+        let counts = [
+            (
+                self.count_water_fx,
+                "bricks with the water effect written as glass",
+            ),
+            (
+                self.count_water_fx_skipped,
+                "bricks with the water effect skipped",
+            ),
+            (self.count_undulo, "bricks lost their undulo waves"),
+        ];
+        for (count, message) in counts {
+            if count > 0 {
+                writeln!(f, "{} {}", count, message)?;
+            }
+        }
+
+        if !self.animated_fx.is_empty() {
+            let mut effects: Vec<_> = self.animated_fx.iter().collect();
+            effects.sort();
+            writeln!(
+                f,
+                "Bricks with animated color effects, written {}:",
+                match self.animated_fx_mode {
+                    AnimatedFx::Plastic => "as plastic",
+                    AnimatedFx::Glow => "glowing",
+                    AnimatedFx::Mark => "in magenta",
+                }
+            )?;
+            for (name, count) in effects {
+                writeln!(f, "  {}: {}", name, count)?;
+            }
+        }
+
+        let counts = [
+            (self.count_duplicates, "duplicate bricks removed"),
+            (self.count_baseplates_stripped, "baseplates removed"),
+            (
+                self.count_baseplate_added,
+                "baseplate bricks added under the build",
+            ),
+            (
+                self.count_terrain_merged,
+                "terrain bricks merged into larger ones",
+            ),
+        ];
+        for (count, message) in counts {
+            if count > 0 {
+                writeln!(f, "{} {}", count, message)?;
+            }
+        }
+
+        for (pass, removed) in &self.pipeline_removed {
+            if *removed > 0 {
+                writeln!(f, "{} bricks removed by the {} pass", removed, pass)?;
+            }
+        }
+
+        if self.count_snapped > 0 {
+            writeln!(
+                f,
+                "{} bricks moved onto the grid by {} units in total",
+                self.count_snapped, self.snap_adjustment
+            )?;
+        }
+
+        if self.count_out_of_range > 0 {
+            writeln!(
+                f,
+                "{} bricks are outside of Brickadia's coordinate range",
+                self.count_out_of_range
+            )?;
+        }
+
+        write!(
+            f,
+            "{} of {} bricks converted successfully to {} bricks",
+            self.count_success,
+            self.count_success + self.count_failure,
+            self.write_data.bricks.len(),
+        )
+    }
 }
 
 pub fn convert(reader: bl_save::Reader<impl BufRead>) -> io::Result<ConvertReport> {
+    convert_with_options(reader, &ConvertOptions::default())
+}
+
+/// Converts a bls file read from `reader`, writing the brs file to `writer`.
+pub fn convert_io(
+    mut reader: impl BufRead,
+    mut writer: impl Write,
+    options: &ConvertOptions,
+) -> io::Result<ConvertReport> {
+    let format = format::detect(&mut reader)?;
+    let reader = bl_save::Reader::new(reader)?;
+    let mut report = convert_with_options(reader, options)?;
+    report.format = format;
+    brs::write_save(&mut writer, &report.write_data)?;
+    writer.flush()?;
+    Ok(report)
+}
+
+pub fn convert_with_options(
+    reader: bl_save::Reader<impl BufRead>,
+    options: &ConvertOptions,
+) -> io::Result<ConvertReport> {
+    if options.checkpoint.is_some() && options.provenance {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "checkpoints can't be used with provenance",
+        ));
+    }
+
+    let max_bricks = options
+        .memory_limit
+        .map_or(usize::MAX, |limit| (limit / MEMORY_PER_BRICK) as usize);
+
+    let source_colors = reader.colors().to_vec();
+    let source_alpha: Vec<f32> = source_colors.iter().map(|c| c.3).collect();
+
+    let stock_colorset = color::is_stock_colorset(&source_colors);
+    let curated = color::Curated {
+        color_space: options.color_space,
+    };
+    let curated_colors = stock_colorset && options.curated_colors && options.color_mapper.is_none();
+    let color_mapper: &dyn ColorMapper = match &options.color_mapper {
+        Some(mapper) => mapper.as_ref(),
+        None if curated_colors => &curated,
+        None => &options.color_space,
+    };
+
     let data = brs::WriteData {
-        map: String::from("Unknown"),
-        author: brs::User {
-            id: Uuid::nil(),
-            name: String::from("Unknown"),
-        },
+        map: options.map.clone(),
+        author: options.author.clone(),
         description: reader.description().to_string(),
         save_time: Utc::now(),
         mods: vec![],
         brick_assets: vec![],
-        colors: reader.colors().iter().map(|c| map_color(*c)).collect(),
-        materials: FIXED_MATERIAL_TABLE
+        colors: reader
+            .colors()
             .iter()
-            .map(|s| String::from(*s))
+            .map(|c| color_mapper.map(*c))
             .collect(),
+        materials: options.materials.clone(),
         brick_owners: vec![brs::User {
-            id: Uuid::from_bytes([u8::max_value(); 16]),
+            id: Uuid::from_bytes([u8::MAX; 16]),
             name: String::from("PUBLIC"),
         }],
-        bricks: Vec::with_capacity(reader.brick_count().unwrap_or(100).min(10_000_000)),
+        bricks: Vec::with_capacity(
+            reader
+                .brick_count()
+                .unwrap_or(100)
+                .min(10_000_000)
+                .min(max_bricks),
+        ),
     };
 
     let mut converter = Converter {
         write_data: data,
         asset_map: HashMap::new(),
+        material_map: options
+            .materials
+            .iter()
+            .enumerate()
+            .map(|(index, material)| (material.clone(), index))
+            .collect(),
+        owner_map: HashMap::new(),
         unknown_ui_names: HashMap::new(),
+        rejected_ui_names: HashMap::new(),
+        mapping_cache: HashMap::new(),
+        scale: options.scale,
+        target: options.target,
+        substituted_assets: HashMap::new(),
     };
 
     let mut count_success = 0;
     let mut count_failure = 0;
+    let mut failure_reasons: HashMap<FailureReason, usize> = HashMap::new();
+    let mut count_out_of_range = 0;
+    let mut count_owner_filtered = 0;
+    let mut count_invisible_skipped = 0;
+    let mut count_water_skipped = 0;
+    let mut count_noncolliding_skipped = 0;
+    let mut count_unknown_placeholders = 0;
+    let mut count_parse_errors = 0;
+    let mut consecutive_parse_errors = 0;
 
     let mut non_prio = Vec::new();
+    let mut provenance = Vec::new();
+    let mut non_prio_provenance = Vec::new();
+    let mut placeholders = Vec::new();
+    let mut dropped_prints = Vec::new();
+    let mut geometry_checked = HashSet::new();
+    let mut geometry_issues = Vec::new();
+    let mut prints = Vec::new();
+    let mut named_bricks = Vec::new();
+    let mut music_bricks = Vec::new();
+    let mut count_dropped_events = 0;
+    let mut portals = Vec::new();
+    let mut minigame_bricks = Vec::new();
+    let mut baseplates = Vec::new();
+    let mut approximate_ui_names: HashMap<String, usize> = HashMap::new();
+    let mut animated_fx: HashMap<String, usize> = HashMap::new();
+    let mut count_water_fx = 0;
+    let mut count_water_fx_skipped = 0;
+    let mut count_undulo = 0;
+    let mut asset_counts: Vec<AssetCount> = Vec::new();
 
-    for from in reader {
-        let from = from?;
-        let option = converter.map_brick(&from);
+    let mut checkpoint_state = None;
+    let mut resume_from = 0;
+    // How many of the bricks and non-priority bricks are in batches already
+    let mut saved = (0, 0);
+    if let Some(checkpoint) = &options.checkpoint {
+        let description = converter.write_data.description.clone();
+        let brick_count = reader.brick_count();
+        let options_hash = checkpoint::options_hash(options);
+        let mut state = checkpoint::State::new(&description, brick_count, &options_hash);
+        if let Some(resumed) = checkpoint.load(&description, brick_count, &options_hash)? {
+            state = resumed.state;
+            converter.restore(&state)?;
+            converter.write_data.bricks = resumed.bricks;
+            non_prio = resumed.non_priority;
+            baseplates = state.baseplates.clone();
+            checkpoint::Tally {
+                count_success,
+                count_failure,
+                failure_reasons,
+                count_out_of_range,
+                count_owner_filtered,
+                count_invisible_skipped,
+                count_water_skipped,
+                count_noncolliding_skipped,
+                count_unknown_placeholders,
+                count_parse_errors,
+                count_dropped_events,
+                count_water_fx,
+                count_water_fx_skipped,
+                count_undulo,
+                placeholders,
+                dropped_prints,
+                geometry_checked,
+                geometry_issues,
+                prints,
+                named_bricks,
+                music_bricks,
+                portals,
+                minigame_bricks,
+                approximate_ui_names,
+                animated_fx,
+                asset_counts,
+            } = state.tally.clone();
+            resume_from = state.next_source_index;
+            saved = (converter.write_data.bricks.len(), non_prio.len());
+            log::info!("resuming from brick {}", resume_from);
+        }
+        checkpoint_state = Some(state);
+    }
+
+    for (source_index, from) in reader.enumerate() {
+        if source_index < resume_from {
+            continue;
+        }
+
+        if let Some(limit) = options.memory_limit {
+            if converter.write_data.bricks.len() + non_prio.len() > max_bricks {
+                return Err(memory_error(limit));
+            }
+        }
+
+        if let (Some(checkpoint), Some(state)) = (&options.checkpoint, &mut checkpoint_state) {
+            if checkpoint.batch_size > 0
+                && source_index > resume_from
+                && source_index % checkpoint.batch_size == 0
+            {
+                converter.save_state(state);
+                state.next_source_index = source_index;
+                state.baseplates = baseplates.clone();
+                state.tally = checkpoint::Tally {
+                    count_success,
+                    count_failure,
+                    failure_reasons: failure_reasons.clone(),
+                    count_out_of_range,
+                    count_owner_filtered,
+                    count_invisible_skipped,
+                    count_water_skipped,
+                    count_noncolliding_skipped,
+                    count_unknown_placeholders,
+                    count_parse_errors,
+                    count_dropped_events,
+                    count_water_fx,
+                    count_water_fx_skipped,
+                    count_undulo,
+                    placeholders: placeholders.clone(),
+                    dropped_prints: dropped_prints.clone(),
+                    geometry_checked: geometry_checked.clone(),
+                    geometry_issues: geometry_issues.clone(),
+                    prints: prints.clone(),
+                    named_bricks: named_bricks.clone(),
+                    music_bricks: music_bricks.clone(),
+                    portals: portals.clone(),
+                    minigame_bricks: minigame_bricks.clone(),
+                    approximate_ui_names: approximate_ui_names.clone(),
+                    animated_fx: animated_fx.clone(),
+                    asset_counts: asset_counts.clone(),
+                };
+                checkpoint.save(
+                    state,
+                    &converter.write_data.bricks[saved.0..],
+                    &non_prio[saved.1..],
+                )?;
+                saved = (converter.write_data.bricks.len(), non_prio.len());
+                log::debug!("saved a checkpoint before brick {}", source_index);
+            }
+        }
+
+        let from = match from {
+            Ok(from) => {
+                consecutive_parse_errors = 0;
+                from
+            }
+            Err(e) if options.lenient => {
+                // A truncated file, or a reader that is stuck, has nothing more to give
+                if e.kind() == io::ErrorKind::UnexpectedEof
+                    || consecutive_parse_errors >= MAX_CONSECUTIVE_PARSE_ERRORS
+                {
+                    log::warn!("stopped reading after brick {}: {}", source_index, e);
+                    break;
+                }
+                log::debug!("skipped brick {}: {}", source_index, e);
+                count_failure += 1;
+                *failure_reasons
+                    .entry(FailureReason::ParseError)
+                    .or_default() += 1;
+                count_parse_errors += 1;
+                consecutive_parse_errors += 1;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !options.only_owners.is_empty()
+            && !extra::owner_id(&from).is_some_and(|id| options.only_owners.contains(&id))
+        {
+            count_owner_filtered += 1;
+            continue;
+        }
+
+        if options.skip_invisible && !from.base.rendering {
+            count_invisible_skipped += 1;
+            continue;
+        }
+
+        if options.skip_noncolliding && !from.base.collision {
+            count_noncolliding_skipped += 1;
+            continue;
+        }
+
+        if options.skip_water && mappings::is_water(&from.base.ui_name) {
+            count_water_skipped += 1;
+            continue;
+        }
+
+        let water_fx = from.base.shape_fx == SHAPE_FX_WATER;
+        if water_fx && options.skip_water {
+            count_water_fx_skipped += 1;
+            continue;
+        }
+
+        if let Some(name) = extra::name(&from) {
+            named_bricks.push(NamedBrick {
+                name: name.to_string(),
+                ui_name: from.base.ui_name.clone(),
+                position: source_position(&from),
+            });
+        }
+
+        if extra::has_events(&from) {
+            count_dropped_events += 1;
+        }
+
+        if mappings::is_portal(&from.base.ui_name) {
+            portals.push(Portal {
+                ui_name: from.base.ui_name.clone(),
+                name: extra::name(&from).map(String::from),
+                targets: extra::teleport_targets(&from),
+                position: source_position(&from),
+            });
+        }
+
+        if let Some(kind) = mappings::minigame_kind(&from.base.ui_name) {
+            minigame_bricks.push(MinigameBrick {
+                kind,
+                ui_name: from.base.ui_name.clone(),
+                name: extra::name(&from).map(String::from),
+                position: source_position(&from),
+            });
+        }
 
-        let mappings = match option {
-            Some(mappings) => {
+        if let Some(track) = extra::music(&from) {
+            music_bricks.push(MusicBrick {
+                track,
+                ui_name: from.base.ui_name.clone(),
+                position: source_position(&from),
+            });
+        }
+
+        let placeholder = if options.placeholders {
+            placeholder(&from)
+        } else {
+            None
+        };
+
+        let option = match placeholder {
+            Some(mapping) => {
+                placeholders.push(Placeholder {
+                    ui_name: from.base.ui_name.clone(),
+                    position: source_position(&from),
+                });
+                Ok(("placeholder", mapping.into()))
+            }
+            None => converter.map_brick(&from),
+        };
+
+        let mapped = option.is_ok();
+        let (rule, mappings) = match option {
+            Ok(mappings) => {
                 count_success += 1;
                 mappings
             }
-            None => {
+            Err(reason) if options.placeholder_unknown => {
+                count_failure += 1;
+                *failure_reasons.entry(reason).or_default() += 1;
+                count_unknown_placeholders += 1;
+                ("unknown placeholder", unknown_placeholder(&from).into())
+            }
+            Err(reason) => {
                 count_failure += 1;
+                *failure_reasons.entry(reason).or_default() += 1;
                 continue;
             }
         };
 
-        for BrickDesc {
-            asset,
-            mut size,
-            offset,
-            rotation_offset,
-            color_override,
-            mut direction_override,
-            non_priority,
-            microwedge_rotate,
-            inverted_modter_rotate,
-            inverted_wedge_rotate,
-        } in mappings
+        if options.check_geometry
+            && mappings.len() > 1
+            && geometry_checked.insert(from.base.ui_name.clone())
         {
+            let messages = lint::check_decomposition(&from.base.ui_name, &mappings[..]);
+            if !messages.is_empty() {
+                geometry_issues.push(GeometryIssue {
+                    ui_name: from.base.ui_name.clone(),
+                    messages,
+                });
+            }
+        }
+
+        if mappings.iter().any(|desc| desc.approximate) {
+            *approximate_ui_names
+                .entry(from.base.ui_name.clone())
+                .or_default() += 1;
+        }
+
+        if !from.base.print.is_empty()
+            && mappings.iter().any(|desc| desc.print_dropped || desc.decal)
+            && !options.print_assets.contains_key(&from.base.print)
+        {
+            dropped_prints.push(DroppedPrint {
+                ui_name: from.base.ui_name.clone(),
+                print: from.base.print.clone(),
+                position: source_position(&from),
+            });
+        }
+
+        let owner_index = match &options.owner {
+            Some(user) => converter.single_owner(user),
+            None => converter.owner(&from, &options.owners),
+        };
+
+        let animated = animated_fx_name(from.base.color_fx);
+        if let Some(name) = animated {
+            *animated_fx.entry(name.to_string()).or_default() += 1;
+        }
+        let mark_animated = animated.is_some() && options.animated_fx == AnimatedFx::Mark;
+
+        // Water bricks keep their color, made as see-through as the water add-ons
+        let water_color = if water_fx {
+            count_water_fx += 1;
+            let (r, g, b, a) = source_colors
+                .get(usize::from(from.base.color_index))
+                .copied()
+                .unwrap_or((1.0, 1.0, 1.0, 1.0));
+            Some(color_mapper.map((r, g, b, a.min(WATER_FX_ALPHA))))
+        } else {
+            None
+        };
+        if from.base.shape_fx == SHAPE_FX_UNDULO {
+            count_undulo += 1;
+        }
+
+        let mut dropped_out_of_range = false;
+        for (
+            desc_index,
+            BrickDesc {
+                asset,
+                mut size,
+                offset,
+                rotation_offset,
+                color_override,
+                mut direction_override,
+                non_priority,
+                collision_override,
+                visibility_override,
+                microwedge_rotate,
+                inverted_modter_rotate,
+                inverted_wedge_rotate,
+                print_dropped: _,
+                print_face,
+                approximate: _,
+                decal,
+            },
+        ) in mappings.iter().cloned().enumerate()
+        {
+            let print_asset = options
+                .print_assets
+                .get(&from.base.print)
+                .filter(|_| decal || print_face == Some(PrintFace::Top));
+            if decal && print_asset.is_none() {
+                continue;
+            }
+            let asset = match (print_asset, options.scale) {
+                (Some(print_asset), _) => print_asset.as_str(),
+                (None, Scale::Normal) => asset,
+                (None, Scale::Micro) => micro_asset(asset),
+            };
             let asset_name_index = converter.asset(asset);
             let mut rotation = (from.base.angle + rotation_offset) % 4;
 
             let rotated_xy = rotate_offset((offset.0, offset.1), from.base.angle);
             let offset = (rotated_xy.0, rotated_xy.1, offset.2);
 
+            let origin = brickadia_position(from.base.position);
             let position = (
-                (from.base.position.1 * 20.0) as i32 + offset.0,
-                (from.base.position.0 * 20.0) as i32 + offset.1,
-                (from.base.position.2 * 20.0) as i32 + offset.2,
+                origin.0 + i64::from(offset.0),
+                origin.1 + i64::from(offset.1),
+                origin.2 + i64::from(offset.2),
             );
 
-            let material_index = match from.base.color_fx {
+            let position = match options.scale {
+                Scale::Normal => position,
+                Scale::Micro => (
+                    scale_down(position.0),
+                    scale_down(position.1),
+                    scale_down(position.2),
+                ),
+            };
+
+            let position = match fit_position(position, options) {
+                Ok(position) => position,
+                Err(position) => {
+                    count_out_of_range += 1;
+                    match position {
+                        Some(position) => position,
+                        None => {
+                            dropped_out_of_range = true;
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let color_override = if mark_animated {
+                Some(PLACEHOLDER_COLOR)
+            } else {
+                color_override
+            };
+
+            let (color_index, alpha) = match (color_override, water_color) {
+                (Some([r, g, b, a]), _) => {
+                    let alpha = f32::from(a) / 255.0;
+                    (converter.color(brs::Color::from_rgba(r, g, b, a)) as u32, alpha)
+                }
+                (None, Some(water_color)) => (converter.color(water_color) as u32, WATER_FX_ALPHA),
+                (None, None) => (
+                    u32::from(from.base.color_index),
+                    source_alpha
+                        .get(usize::from(from.base.color_index))
+                        .copied()
+                        .unwrap_or(1.0),
+                ),
+            };
+
+            let material_index = converter.material(match from.base.color_fx {
+                _ if water_fx => BMC_GLASS,
                 3 => BMC_GLOW,
                 1 | 2 => BMC_METALLIC,
+                4..=7 if options.animated_fx == AnimatedFx::Glow => BMC_GLOW,
+                _ if alpha < options.glass_alpha_threshold => BMC_GLASS,
                 _ => BMC_PLASTIC,
-            };
+            });
 
-            let color_index = match color_override {
-                Some(color) => converter.color(color) as u32,
-                None => u32::from(from.base.color_index),
-            };
+            // Sideways parts of a decomposition turn with the source brick by changing
+            // direction, since their rotation is about their own axis
+            if let Some(direction) =
+                direction_override.filter(|d| !microwedge_rotate && is_horizontal(*d))
+            {
+                direction_override = Some(rotate_direction(direction, from.base.angle));
+                rotation = rotation_offset % 4;
+            }
 
             // convert a vertical slope to microwedge
             if microwedge_rotate {
@@ -145,67 +1547,340 @@ pub fn convert(reader: bl_save::Reader<impl BufRead>) -> io::Result<ConvertRepor
             }
 
             // fix odd rotation offsets on inverted ModTer, wedges
-            if (inverted_modter_rotate && (rotation == 1 || rotation == 3)) ||
-                (inverted_wedge_rotate && (rotation == 0 || rotation == 2)) {
+            if (inverted_modter_rotate && (rotation == 1 || rotation == 3))
+                || (inverted_wedge_rotate && (rotation == 0 || rotation == 2))
+            {
                 rotation = (rotation + 2) % 4;
             }
 
+            // At least 32x32 studs and plate height
+            let is_baseplate =
+                from.base.is_baseplate && size.0 >= 160 && size.1 >= 160 && size.2 <= 2;
+
+            if options.scale == Scale::Micro {
+                size = (
+                    scale_down_size(size.0),
+                    scale_down_size(size.1),
+                    scale_down_size(size.2),
+                );
+            }
+
             let brick = brs::Brick {
                 asset_name_index: asset_name_index as u32,
                 size,
                 position,
                 direction: direction_override.unwrap_or(brs::Direction::ZPositive),
                 rotation: rotation.try_into().unwrap(),
-                collision: from.base.collision,
-                visibility: from.base.rendering,
+                collision: collision_override.unwrap_or(from.base.collision),
+                visibility: visibility_override.unwrap_or(from.base.rendering),
                 material_index: material_index as u32,
                 color: brs::ColorMode::Set(color_index),
-                owner_index: BRICK_OWNER as u32,
+                owner_index,
             };
 
+            if let Some(face) = print_face.filter(|_| !from.base.print.is_empty()) {
+                prints.push(PrintPlacement {
+                    print: from.base.print.clone(),
+                    face,
+                    position: brick.position,
+                    direction: brick.direction,
+                    rotation,
+                    facing: print_facing(face, brick.direction, rotation),
+                });
+            }
+
+            if asset_counts.len() <= asset_name_index {
+                asset_counts.resize(asset_name_index + 1, AssetCount::default());
+            }
+            if mappings.len() > 1 {
+                asset_counts[asset_name_index].decomposed += 1;
+            } else {
+                asset_counts[asset_name_index].direct += 1;
+            }
+
+            let brick_provenance = options.provenance.then(|| Provenance {
+                source_index: Some(source_index),
+                ui_name: from.base.ui_name.clone(),
+                rule: Cow::Borrowed(rule),
+                desc_index,
+            });
+
             if non_priority {
                 non_prio.push(brick);
+                non_prio_provenance.extend(brick_provenance);
             } else {
+                if options.strip_baseplates && is_baseplate {
+                    baseplates.push(converter.write_data.bricks.len());
+                }
                 converter.write_data.bricks.push(brick);
+                provenance.extend(brick_provenance);
             }
         }
+
+        // Unknown placeholders already failed with their own reason
+        if dropped_out_of_range && mapped {
+            count_success -= 1;
+            count_failure += 1;
+            *failure_reasons
+                .entry(FailureReason::OutOfRange)
+                .or_default() += 1;
+        }
+    }
+
+    let keep = passes::strip_baseplates(&converter.write_data.bricks, &baseplates);
+    let count_baseplates_stripped = passes::retain_mask(&mut converter.write_data.bricks, &keep);
+    passes::retain_mask(&mut provenance, &keep);
+
+    if options.strict && !converter.unknown_ui_names.is_empty() {
+        let mut ui_names: Vec<_> = converter.unknown_ui_names.iter().collect();
+        ui_names.sort_by(|(an, ac), (bn, bc)| ac.cmp(bc).reverse().then(an.cmp(bn)));
+        let list: Vec<_> = ui_names
+            .into_iter()
+            .map(|(ui_name, count)| format!("{:?} x{}", ui_name, count))
+            .collect();
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{} bricks could not be mapped: {}",
+                count_failure,
+                list.join(", ")
+            ),
+        ));
+    }
+
+    let mut unknown_assets: Vec<String> = if options.known_assets.is_empty() {
+        Vec::new()
+    } else {
+        let known: HashSet<&str> = options.known_assets.iter().map(String::as_str).collect();
+        converter
+            .write_data
+            .brick_assets
+            .iter()
+            .filter(|asset| !known.contains(asset.as_str()))
+            .cloned()
+            .collect()
+    };
+    unknown_assets.sort();
+
+    if options.strict && !unknown_assets.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "bricks use assets Brickadia doesn't have: {}",
+                unknown_assets.join(", ")
+            ),
+        ));
     }
-    
+
+    log::debug!(
+        "mapped {} bricks with {} distinct names and prints",
+        count_success + count_failure,
+        converter
+            .mapping_cache
+            .values()
+            .map(HashMap::len)
+            .sum::<usize>()
+    );
+
     converter.write_data.bricks.append(&mut non_prio);
+    provenance.append(&mut non_prio_provenance);
+
+    let count_baseplate_added = if options.add_baseplate {
+        converter.add_baseplate(options.scale)
+    } else {
+        0
+    };
+
+    if options.provenance {
+        provenance.extend((0..count_baseplate_added).map(|desc_index| Provenance {
+            source_index: None,
+            ui_name: String::new(),
+            rule: Cow::Borrowed("baseplate"),
+            desc_index,
+        }));
+    }
+
+    // Before dedup, so bricks that end up in the same place are removed
+    let (count_snapped, snap_adjustment) = match options.grid_snap {
+        Some(tolerance) => {
+            pipeline::Snap { tolerance }.snap(&mut converter.write_data, options.scale)
+        }
+        None => (0, 0),
+    };
+
+    let count_duplicates = if options.dedup {
+        pipeline::apply(
+            &pipeline::Dedup,
+            &mut converter.write_data,
+            options,
+            &mut provenance,
+        )
+    } else {
+        0
+    };
 
-    Ok(ConvertReport {
+    let count_terrain_merged = if options.merge_terrain {
+        pipeline::apply(
+            &pipeline::MergeTerrain,
+            &mut converter.write_data,
+            options,
+            &mut provenance,
+        )
+    } else {
+        0
+    };
+
+    let pipeline_removed =
+        options
+            .pipeline
+            .run(&mut converter.write_data, options, &mut provenance);
+
+    let overlaps = if options.detect_overlaps {
+        passes::find_overlaps(&converter.write_data.bricks)
+    } else {
+        Vec::new()
+    };
+
+    let owner_brick_counts = owner_brick_counts(&converter.write_data);
+    asset_counts.resize(
+        converter.write_data.brick_assets.len(),
+        AssetCount::default(),
+    );
+
+    let mut report = ConvertReport {
         write_data: converter.write_data,
+        source_colors,
         unknown_ui_names: converter.unknown_ui_names,
+        approximate_ui_names,
+        rejected_ui_names: converter.rejected_ui_names,
         count_success,
         count_failure,
-    })
+        failure_reasons,
+        count_out_of_range,
+        count_duplicates,
+        count_baseplates_stripped,
+        count_baseplate_added,
+        count_terrain_merged,
+        pipeline_removed,
+        count_snapped,
+        snap_adjustment,
+        count_owner_filtered,
+        count_invisible_skipped,
+        count_noncolliding_skipped,
+        count_water_skipped,
+        animated_fx,
+        animated_fx_mode: options.animated_fx,
+        count_water_fx,
+        count_water_fx_skipped,
+        count_undulo,
+        overlaps,
+        owner_brick_counts,
+        asset_counts,
+        count_parse_errors,
+        count_unknown_placeholders,
+        placeholders,
+        geometry_issues,
+        dropped_prints,
+        prints,
+        named_bricks,
+        music_bricks,
+        count_dropped_events,
+        portals,
+        minigame_bricks,
+        unknown_assets,
+        substituted_assets: converter.substituted_assets,
+        format: None,
+        stock_colorset,
+        curated_colors,
+        provenance,
+        warnings: Vec::new(),
+    };
+    report.warnings = warning::collect(&report, options);
+    Ok(report)
 }
 
+type SharedMapping = Arc<[BrickDesc]>;
+
+/// Names the mapping that produced a brick, see `Provenance::rule`.
+type Rule = &'static str;
+
+type MappingResult = Result<(Rule, SharedMapping), FailureReason>;
+
 struct Converter {
     write_data: brs::WriteData,
     asset_map: HashMap<String, usize>,
+    material_map: HashMap<String, usize>,
+    owner_map: HashMap<u32, u32>,
     unknown_ui_names: HashMap<String, usize>,
+    rejected_ui_names: HashMap<String, usize>,
+    /// Mappings by ui name, then print. Shared so big decompositions aren't copied per brick.
+    mapping_cache: HashMap<String, HashMap<String, MappingResult>>,
+    scale: Scale,
+    target: Target,
+    substituted_assets: HashMap<String, String>,
 }
 
 impl Converter {
-    fn map_brick(&mut self, from: &bl_save::Brick) -> Option<BrickMapping> {
-        let mapping = map_brick(from);
+    fn map_brick(&mut self, from: &bl_save::Brick) -> MappingResult {
+        // Only the print can change the mapping of a name, see the brick regex
+        let cached = self
+            .mapping_cache
+            .get(&from.base.ui_name)
+            .and_then(|prints| prints.get(&from.base.print));
 
-        if cfg!(debug_assertions) {
-            println!("mapped '{}' to {:?}", from.base.ui_name, mapping);
-        }
+        let mapping = match cached {
+            Some(mapping) => mapping.clone(),
+            None => {
+                let mapping = match (map_brick(from), self.scale) {
+                    (Some((rule, mapping)), Scale::Normal) => Ok((rule, mapping.into())),
+                    (Some((rule, mapping)), Scale::Micro) => micro_mapping(mapping)
+                        .map(|mapping| (rule, mapping.into()))
+                        .ok_or(FailureReason::NotScalable),
+                    (None, _) if BRICK_MAP_REGEX_SET.is_match(&from.base.ui_name) => {
+                        Err(FailureReason::UnsupportedVariant)
+                    }
+                    (None, _) => Err(FailureReason::UnknownName),
+                };
+                log::trace!("mapped '{}' to {:?}", from.base.ui_name, mapping);
+                self.mapping_cache
+                    .entry(from.base.ui_name.clone())
+                    .or_default()
+                    .insert(from.base.print.clone(), mapping.clone());
+                mapping
+            }
+        };
 
-        if mapping.is_none() {
+        if let Err(reason @ (FailureReason::UnknownName | FailureReason::UnsupportedVariant)) =
+            mapping
+        {
             *self
                 .unknown_ui_names
                 .entry(from.base.ui_name.clone())
                 .or_default() += 1;
+
+            if reason == FailureReason::UnsupportedVariant {
+                *self
+                    .rejected_ui_names
+                    .entry(from.base.ui_name.clone())
+                    .or_default() += 1;
+            }
         }
 
         mapping
     }
 
     fn asset(&mut self, asset_name: &str) -> usize {
+        let asset_name = match self.target.substitute(asset_name) {
+            Some(substitute) => {
+                if !self.substituted_assets.contains_key(asset_name) {
+                    self.substituted_assets
+                        .insert(asset_name.to_string(), substitute.to_string());
+                }
+                substitute
+            }
+            None => asset_name,
+        };
+
         if let Some(index) = self.asset_map.get(asset_name) {
             return *index;
         }
@@ -217,6 +1892,142 @@ impl Converter {
         index
     }
 
+    fn material(&mut self, material_name: &str) -> usize {
+        if let Some(index) = self.material_map.get(material_name) {
+            return *index;
+        }
+
+        let index = self.write_data.materials.len();
+        self.write_data.materials.push(material_name.to_string());
+        self.material_map.insert(material_name.to_string(), index);
+
+        index
+    }
+
+    fn owner(&mut self, from: &bl_save::Brick, owners: &OwnerMap) -> u32 {
+        let bl_id = match extra::owner_id(from) {
+            Some(bl_id) => bl_id,
+            None => return BRICK_OWNER as u32,
+        };
+
+        if let Some(index) = self.owner_map.get(&bl_id) {
+            return *index;
+        }
+
+        let index = match owners.user(bl_id) {
+            Some(user) => {
+                self.write_data.brick_owners.push(user);
+                self.write_data.brick_owners.len() as u32
+            }
+            None => BRICK_OWNER as u32,
+        };
+
+        self.owner_map.insert(bl_id, index);
+        index
+    }
+
+    /// Picks up the tables saved by `save_state`, see `ConvertOptions::checkpoint`.
+    fn restore(&mut self, state: &checkpoint::State) -> io::Result<()> {
+        self.write_data.brick_assets = state.brick_assets.clone();
+        self.asset_map = index_map(&state.brick_assets);
+        self.write_data.colors = state.colors.iter().map(json::color_from_json).collect();
+        self.write_data.materials = state.materials.clone();
+        self.material_map = index_map(&state.materials);
+        self.write_data.brick_owners = state
+            .brick_owners
+            .iter()
+            .cloned()
+            .map(json::UserJson::into_user)
+            .collect::<Result<_, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.owner_map = state.owner_map.iter().copied().collect();
+        self.unknown_ui_names = state.unknown_ui_names.clone();
+        self.rejected_ui_names = state.rejected_ui_names.clone();
+        self.substituted_assets = state.substituted_assets.clone();
+        Ok(())
+    }
+
+    fn save_state(&self, state: &mut checkpoint::State) {
+        state.brick_assets = self.write_data.brick_assets.clone();
+        state.colors = self
+            .write_data
+            .colors
+            .iter()
+            .map(json::color_to_json)
+            .collect();
+        state.materials = self.write_data.materials.clone();
+        state.brick_owners = self
+            .write_data
+            .brick_owners
+            .iter()
+            .map(json::UserJson::from_user)
+            .collect();
+        state.owner_map = self.owner_map.iter().map(|(k, v)| (*k, *v)).collect();
+        state.unknown_ui_names = self.unknown_ui_names.clone();
+        state.rejected_ui_names = self.rejected_ui_names.clone();
+        state.substituted_assets = self.substituted_assets.clone();
+    }
+
+    /// The owner index of `user`, for `ConvertOptions::owner`.
+    fn single_owner(&mut self, user: &brs::User) -> u32 {
+        if self.write_data.brick_owners.len() == 1 {
+            self.write_data.brick_owners.push(user.clone());
+        }
+        self.write_data.brick_owners.len() as u32
+    }
+
+    /// Covers the footprint of the build with baseplates just below its lowest point.
+    /// Returns how many bricks were added.
+    fn add_baseplate(&mut self, scale: Scale) -> usize {
+        // The size of a stud
+        let (asset, stud, height) = match scale {
+            Scale::Normal => ("PB_DefaultBrick", 10, 2),
+            Scale::Micro => ("PB_DefaultMicroBrick", 2, 1),
+        };
+
+        let (min, max, bottom) = match passes::footprint(&self.write_data.bricks) {
+            Some(footprint) => footprint,
+            None => return 0,
+        };
+
+        let snap_down = |u: i64| u.div_euclid(stud) * stud;
+        let snap_up = |u: i64| -(-u).div_euclid(stud) * stud;
+        let (x0, y0) = (snap_down(min[0]), snap_down(min[1]));
+        let (x1, y1) = (snap_up(max[0]), snap_up(max[1]));
+
+        let asset_name_index = self.asset(asset) as u32;
+        let material_index = self.material(BMC_PLASTIC) as u32;
+        let color_index = self.color(brs::Color::from_rgba(80, 80, 80, 255)) as u32;
+        let z = bottom - i64::from(height);
+        let mut count = 0;
+
+        let mut x = x0;
+        while x < x1 {
+            let width = (x1 - x).min(MAX_SIDE);
+            let mut y = y0;
+            while y < y1 {
+                let length = (y1 - y).min(MAX_SIDE);
+                self.write_data.bricks.push(brs::Brick {
+                    asset_name_index,
+                    size: ((width / 2) as u32, (length / 2) as u32, height),
+                    position: ((x + width / 2) as i32, (y + length / 2) as i32, z as i32),
+                    direction: brs::Direction::ZPositive,
+                    rotation: brs::Rotation::Deg0,
+                    collision: true,
+                    visibility: true,
+                    material_index,
+                    color: brs::ColorMode::Set(color_index),
+                    owner_index: BRICK_OWNER as u32,
+                });
+                count += 1;
+                y += length;
+            }
+            x += width;
+        }
+
+        count
+    }
+
     fn color(&mut self, color: brs::Color) -> usize {
         // TODO: Optimize lookup with a map
         for (index, other) in self.write_data.colors.iter().enumerate() {
@@ -231,36 +2042,215 @@ impl Converter {
     }
 }
 
-fn map_brick(from: &bl_save::Brick) -> Option<BrickMapping> {
+/// Rough memory per converted brick while converting and writing the save, in bytes:
+/// the brick, room for the list of bricks to grow, and brs's buffers.
+const MEMORY_PER_BRICK: u64 = 160;
+
+fn memory_error(limit: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::OutOfMemory,
+        format!(
+            "the converted bricks would take more than the memory limit of {} MB",
+            limit / 1_000_000
+        ),
+    )
+}
+
+/// Each name's index in `names`.
+fn index_map(names: &[String]) -> HashMap<String, usize> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.clone(), index))
+        .collect()
+}
+
+fn map_brick(from: &bl_save::Brick) -> Option<(Rule, BrickMapping)> {
     let ui_name = from.base.ui_name.as_str();
 
-    if let Some(mapping) = BRICK_MAP_LITERAL.get(ui_name) {
-        return Some(mapping.clone());
+    if let Some(mapping) = BRICK_MAP_EXACT.get(ui_name) {
+        return Some(("exact", mapping.to_vec()));
+    }
+
+    if let Some(mapping) = BRICK_MAP_APPROXIMATE.get(ui_name) {
+        return Some((
+            "approximate",
+            mapping
+                .iter()
+                .map(|desc| desc.clone().approximate(true))
+                .collect(),
+        ));
+    }
+
+    let index = BRICK_MAP_REGEX_SET.matches(ui_name).into_iter().next()?;
+    let (regex, func) = &BRICK_MAP_REGEX[index];
+    let mapping = func(regex.captures(ui_name)?, from);
+    if mapping.is_none() {
+        log::debug!("'{}' matched {} but was rejected", ui_name, regex);
+    }
+    Some((regex.as_str(), mapping?))
+}
+
+/// How the bricks of a ui name would be mapped, see `coverage`. Ordered from best to
+/// worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MappingKind {
+    /// A literal mapping that reproduces the Blockland brick.
+    Exact,
+    /// A literal mapping that only resembles the Blockland brick.
+    Approximate,
+    /// A mapping for a family of names, such as `AxB` bricks.
+    Regex,
+    /// No mapping, the bricks would be left out.
+    Unmapped,
+}
+
+/// The bricks of one ui name in a save, see `coverage`.
+#[derive(Debug, Clone, Copy)]
+pub struct NameCoverage {
+    /// The worst mapping of any of the bricks, since prints can change it.
+    pub kind: MappingKind,
+    pub count: usize,
+}
+
+/// Finds how each ui name in a bls file would be mapped, without converting it.
+pub fn coverage(
+    reader: bl_save::Reader<impl BufRead>,
+) -> io::Result<HashMap<String, NameCoverage>> {
+    let mut names: HashMap<String, NameCoverage> = HashMap::new();
+    let mut cache: HashMap<(String, String), MappingKind> = HashMap::new();
+
+    for from in reader {
+        let from = from?;
+
+        let key = (from.base.ui_name.clone(), from.base.print.clone());
+        let kind = *cache.entry(key).or_insert_with(|| match map_brick(&from) {
+            Some(("exact", _)) => MappingKind::Exact,
+            Some(("approximate", _)) => MappingKind::Approximate,
+            Some(_) => MappingKind::Regex,
+            None => MappingKind::Unmapped,
+        });
+
+        let name = names
+            .entry(from.base.ui_name)
+            .or_insert(NameCoverage { kind, count: 0 });
+        name.kind = name.kind.max(kind);
+        name.count += 1;
     }
 
-    for (regex, func) in BRICK_MAP_REGEX.iter() {
-        if let Some(captures) = regex.captures(ui_name) {
-            return func(captures, from);
+    Ok(names)
+}
+
+/// Counts the bricks of each entry of `brick_owners`. Owner index 0 (public) counts
+/// towards the first entry, the PUBLIC user.
+pub fn owner_brick_counts(data: &brs::WriteData) -> Vec<usize> {
+    let mut counts = vec![0; data.brick_owners.len()];
+    for brick in &data.bricks {
+        let index = (brick.owner_index as usize).max(1) - 1;
+        if let Some(count) = counts.get_mut(index) {
+            *count += 1;
         }
     }
+    counts
+}
+
+/// The position of a source brick in output coordinates, before any offsets.
+fn source_position(from: &bl_save::Brick) -> (i32, i32, i32) {
+    let (x, y, z) = brickadia_position(from.base.position);
+    (x as i32, y as i32, z as i32)
+}
+
+const PLACEHOLDER_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// Blockland `shape_fx` values.
+const SHAPE_FX_UNDULO: u8 = 1;
+const SHAPE_FX_WATER: u8 = 2;
+
+/// Most opacity of bricks with the water shape effect, matching the water add-ons.
+const WATER_FX_ALPHA: f32 = 0.5;
+
+/// The name of a Blockland color effect that animates, which Brickadia can't show.
+fn animated_fx_name(color_fx: u8) -> Option<&'static str> {
+    match color_fx {
+        4 => Some("blink"),
+        5 => Some("swirl"),
+        6 => Some("rainbow"),
+        7 => Some("unstable"),
+        _ => None,
+    }
+}
 
-    None
+fn placeholder(from: &bl_save::Brick) -> Option<BrickMapping> {
+    let size = PLACEHOLDER_SIZES.get(from.base.ui_name.as_str())?;
+    Some(vec![BrickDesc::new("PB_DefaultBrick")
+        .size(*size)
+        .color_override(PLACEHOLDER_COLOR)])
 }
 
-fn map_color((r, g, b, a): (f32, f32, f32, f32)) -> brs::Color {
+/// A brick standing in for one that can't be mapped, sized from the first dimensions in
+/// its name (like `4x8` or `2x2x3`), or 1x1x1 if there are none.
+fn unknown_placeholder(from: &bl_save::Brick) -> BrickMapping {
+    lazy_static::lazy_static! {
+        static ref DIMENSIONS: regex::Regex =
+            regex::Regex::new(r"(\d+)x(\d+)(?:x(\d+)|([Ff]))?").unwrap();
+    }
+
+    // Keep absurd names from making absurd bricks
+    let studs = |u: &str| u.parse::<u32>().unwrap_or(1).clamp(1, 64);
+
+    let size = match DIMENSIONS.captures(&from.base.ui_name) {
+        Some(captures) => (
+            studs(&captures[1]) * 5,
+            studs(&captures[2]) * 5,
+            if captures.get(4).is_some() {
+                2
+            } else {
+                captures.get(3).map_or(1, |z| studs(z.as_str())) * 6
+            },
+        ),
+        None => (5, 5, 6),
+    };
+
+    vec![BrickDesc::new("PB_DefaultBrick")
+        .size(size)
+        .color_override(PLACEHOLDER_COLOR)]
+}
+
+pub(crate) fn map_color((r, g, b, a): (f32, f32, f32, f32), mode: ColorSpaceMode) -> brs::Color {
     // Convert into Unreal color space
     let r = gamma_expansion(r);
     let g = gamma_expansion(g);
     let b = gamma_expansion(b);
-    let a = gamma_expansion(a);
 
-    // Convert to 0-255
-    let r = (r * 255.0).max(0.0).min(255.0) as u8;
-    let g = (g * 255.0).max(0.0).min(255.0) as u8;
-    let b = (b * 255.0).max(0.0).min(255.0) as u8;
-    let a = (a * 255.0).max(0.0).min(255.0) as u8;
+    match mode {
+        ColorSpaceMode::Srgb => brs::Color::from_rgba(
+            unit_to_byte(r),
+            unit_to_byte(g),
+            unit_to_byte(b),
+            unit_to_byte(a),
+        ),
+        ColorSpaceMode::Legacy => {
+            let a = gamma_expansion(a);
+            let truncate = |u: f32| (u * 255.0).clamp(0.0, 255.0) as u8;
+            brs::Color::from_rgba(truncate(r), truncate(g), truncate(b), truncate(a))
+        }
+    }
+}
+
+/// The color of a brick, looking it up in the palette if needed.
+pub(crate) fn resolve_color(data: &brs::WriteData, color: &brs::ColorMode) -> brs::Color {
+    match color {
+        brs::ColorMode::Set(index) => data
+            .colors
+            .get(*index as usize)
+            .copied()
+            .unwrap_or_else(|| brs::Color::from_rgba(255, 255, 255, 255)),
+        brs::ColorMode::Custom(color) => *color,
+    }
+}
 
-    brs::Color::from_rgba(r, g, b, a)
+pub(crate) fn unit_to_byte(u: f32) -> u8 {
+    (u * 255.0).round().clamp(0.0, 255.0) as u8
 }
 
 fn gamma_expansion(u: f32) -> f32 {
@@ -271,13 +2261,104 @@ fn gamma_expansion(u: f32) -> f32 {
     base.powf(2.4)
 }
 
-fn rotate_offset(mut offset: (i32, i32), angle: u8) -> (i32, i32) {
-    for _ in 0..angle {
-        offset = rotate_90_2d(offset);
+/// Largest side of a procedural brick that bls2brs writes, in units.
+const MAX_SIDE: i64 = 640;
+
+/// Procedural assets that look the same when stretched, for `ConvertOptions::merge_terrain`.
+const RESIZABLE_ASSETS: &[&str] = &[
+    "PB_DefaultBrick",
+    "PB_DefaultMicroBrick",
+    "PB_DefaultTile",
+    "PB_DefaultSmoothTile",
+];
+
+/// Box-shaped procedural assets, which `Scale::Micro` writes as micro bricks of a fifth
+/// of their size.
+const MICRO_ASSETS: &[&str] = &[
+    "PB_DefaultBrick",
+    "PB_DefaultMicroBrick",
+    "PB_DefaultTile",
+    "PB_DefaultSmoothTile",
+    "PB_DefaultStudded",
+];
+
+fn micro_asset(asset: &'static str) -> &'static str {
+    if MICRO_ASSETS.contains(&asset) {
+        "PB_DefaultMicroBrick"
+    } else {
+        asset
+    }
+}
+
+/// Fits a mapping to `Scale::Micro`. Procedural assets that aren't boxes become bricks
+/// of their size, marked approximate. `None` if it has a non-procedural asset, which
+/// can't be scaled.
+fn micro_mapping(mapping: BrickMapping) -> Option<BrickMapping> {
+    mapping
+        .into_iter()
+        .map(|mut desc| {
+            if MICRO_ASSETS.contains(&desc.asset) {
+                return Some(desc);
+            }
+            // Non-procedural assets have no size
+            if desc.size == (0, 0, 0) {
+                return None;
+            }
+
+            desc.asset = "PB_DefaultBrick";
+            desc.approximate = true;
+            if desc.print_face.take().is_some() {
+                desc.print_dropped = true;
+            }
+            Some(desc)
+        })
+        .collect()
+}
+
+/// Divides a position by 5 for `Scale::Micro`, rounding to nearest.
+fn scale_down(u: i64) -> i64 {
+    (u as f64 / 5.0).round() as i64
+}
+
+/// Divides a size by 5 for `Scale::Micro`, keeping it at least 1.
+fn scale_down_size(u: u32) -> u32 {
+    ((u + 2) / 5).max(1)
+}
+
+type Position = (i32, i32, i32);
+
+/// Converts a position to output coordinates, returning `Err` if it is out of range
+/// along with the position to use instead (if any) according to the policy.
+fn fit_position(
+    (x, y, z): (i64, i64, i64),
+    options: &ConvertOptions,
+) -> Result<Position, Option<Position>> {
+    let limit = i64::from(options.coordinate_limit);
+    let fit = |u: i64, limit: i64| u.clamp(-limit, limit) as i32;
+
+    if x.abs() <= limit && y.abs() <= limit && z.abs() <= limit {
+        return Ok((x as i32, y as i32, z as i32));
+    }
+
+    match options.out_of_range {
+        OutOfRangePolicy::Keep => {
+            let max = i64::from(i32::MAX);
+            Err(Some((fit(x, max), fit(y, max), fit(z, max))))
+        }
+        OutOfRangePolicy::Clamp => Err(Some((fit(x, limit), fit(y, limit), fit(z, limit)))),
+        OutOfRangePolicy::Drop => Err(None),
     }
-    offset
 }
 
-fn rotate_90_2d<X, Y: Neg>((x, y): (X, Y)) -> (<Y as Neg>::Output, X) {
-    (-y, x)
+/// The way a print on `face` points on a brick with this direction and rotation. At
+/// rotation 0 the front of an upright brick faces X+.
+fn print_facing(face: PrintFace, direction: brs::Direction, rotation: u8) -> brs::Direction {
+    match face {
+        PrintFace::Top => direction,
+        PrintFace::Front | PrintFace::Slope if !is_horizontal(direction) => {
+            rotate_direction(brs::Direction::XPositive, rotation)
+        }
+        // A sideways brick has no front, so use the way its top points
+        PrintFace::Front | PrintFace::Slope => direction,
+    }
 }
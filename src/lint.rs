@@ -0,0 +1,192 @@
+//! Sanity checks over the mapping tables, for `bls2brs lint` and
+//! `ConvertOptions::check_geometry`.
+
+use crate::{
+    assets::KNOWN_ASSETS,
+    mappings::{BRICK_MAP_APPROXIMATE, BRICK_MAP_EXACT, BRICK_MAP_REGEX, BRICK_MAP_REGEX_SET},
+    passes,
+    types::BrickDesc,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::convert::TryInto;
+
+lazy_static! {
+    /// Blockland names start with the footprint in studs, and the height in bricks or F for
+    /// a plate.
+    static ref FOOTPRINT: Regex = Regex::new(r"^(\d+)x(\d+)(?:x(\d+)|([Ff]))?\b").unwrap();
+}
+
+/// A problem with one brick of a mapping.
+#[derive(Debug, Clone)]
+pub struct MappingIssue {
+    pub ui_name: &'static str,
+    /// Index into the mapping's bricks.
+    pub desc_index: usize,
+    pub message: String,
+}
+
+/// Checks every literal mapping, including that it only uses assets in
+/// `assets::KNOWN_ASSETS`, returning the problems found sorted by name. Also makes
+/// sure the brick regexes compile, which panics if one doesn't.
+///
+/// Regex mappings depend on the name they match, so only their patterns are checked.
+pub fn lint_mappings() -> Vec<MappingIssue> {
+    assert_eq!(BRICK_MAP_REGEX.len(), BRICK_MAP_REGEX_SET.len());
+
+    let mut issues = Vec::new();
+
+    for (&ui_name, &mapping) in BRICK_MAP_EXACT
+        .entries()
+        .chain(BRICK_MAP_APPROXIMATE.entries())
+    {
+        let extent = name_footprint(ui_name).map(|(footprint, height)| {
+            // Mappings may be laid out along either axis
+            let side = footprint[0].max(footprint[1]);
+            [side, side, height.unwrap_or(6)]
+        });
+
+        for (desc_index, desc) in mapping.iter().enumerate() {
+            for message in lint_desc(desc, extent) {
+                issues.push(MappingIssue {
+                    ui_name,
+                    desc_index,
+                    message,
+                });
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| (issue.ui_name, issue.desc_index));
+    issues
+}
+
+fn lint_desc(desc: &BrickDesc, extent: Option<[i32; 3]>) -> Vec<String> {
+    let mut messages = Vec::new();
+    let (sx, sy, sz) = desc.size;
+
+    if desc.asset.starts_with("PB_") && (sx == 0 || sy == 0 || sz == 0) {
+        messages.push(format!(
+            "procedural asset {} has a zero size {:?}",
+            desc.asset, desc.size
+        ));
+    }
+
+    if !KNOWN_ASSETS.contains(&desc.asset) {
+        messages.push(format!(
+            "asset {} is not a known Brickadia asset",
+            desc.asset
+        ));
+    }
+
+    if desc.rotation_offset > 3 {
+        messages.push(format!(
+            "rotation offset {} is not 0-3",
+            desc.rotation_offset
+        ));
+    }
+
+    if let Some(extent) = extent {
+        let (x, y, z) = desc.offset;
+        if x.abs() > extent[0] || y.abs() > extent[1] || z.abs() > extent[2] {
+            messages.push(format!(
+                "offset {:?} is outside the source brick's half-extents {:?}",
+                desc.offset, extent
+            ));
+        }
+    }
+
+    messages
+}
+
+/// The half-extents in units of a brick from the footprint at the start of its name, and
+/// its half height if the name has one.
+fn name_footprint(ui_name: &str) -> Option<([i32; 2], Option<i32>)> {
+    let captures = FOOTPRINT.captures(ui_name)?;
+    let width: i32 = captures.get(1)?.as_str().parse().ok()?;
+    let length: i32 = captures.get(2)?.as_str().parse().ok()?;
+    let height = match (captures.get(3), captures.get(4)) {
+        (Some(height), _) => Some(height.as_str().parse::<i32>().ok()? * 6),
+        (None, Some(_)) => Some(2),
+        (None, None) => None,
+    };
+    Some(([width * 5, length * 5], height))
+}
+
+/// Compares the bounding box of a decomposition at rotation 0 with the footprint in the
+/// source brick's name, describing each side that overhangs it or falls short of it by
+/// more than a unit. Microwedge parts are left out, since they're reshaped when placed, and
+/// so are decals, which sit on a face.
+pub(crate) fn check_decomposition(ui_name: &str, mapping: &[BrickDesc]) -> Vec<String> {
+    let (footprint, height) = match name_footprint(ui_name) {
+        Some(footprint) => footprint,
+        None => return Vec::new(),
+    };
+
+    let mut bounds: Option<([i64; 3], [i64; 3])> = None;
+    for desc in mapping
+        .iter()
+        .filter(|desc| !desc.microwedge_rotate && !desc.decal)
+    {
+        let (min, max) = passes::brick_bounds(&brs::Brick {
+            asset_name_index: 0,
+            size: desc.size,
+            position: desc.offset,
+            direction: desc.direction_override.unwrap_or(brs::Direction::ZPositive),
+            rotation: (desc.rotation_offset % 4).try_into().unwrap(),
+            collision: true,
+            visibility: true,
+            material_index: 0,
+            color: brs::ColorMode::Set(0),
+            owner_index: 0,
+        });
+        bounds = Some(match bounds {
+            Some((all_min, all_max)) => (
+                [0, 1, 2].map(|axis| all_min[axis].min(min[axis])),
+                [0, 1, 2].map(|axis| all_max[axis].max(max[axis])),
+            ),
+            None => (min, max),
+        });
+    }
+
+    let (min, max) = match bounds {
+        Some(bounds) => bounds,
+        None => return Vec::new(),
+    };
+
+    // The footprint may be laid out along either axis, so use the closer fit
+    let fit = |[x, y]: [i32; 2]| {
+        let x = i64::from(x);
+        let y = i64::from(y);
+        (min[0] + x).abs() + (max[0] - x).abs() + (min[1] + y).abs() + (max[1] - y).abs()
+    };
+    let [x, y] = if fit(footprint) <= fit([footprint[1], footprint[0]]) {
+        footprint
+    } else {
+        [footprint[1], footprint[0]]
+    };
+
+    let mut expected = vec![("X", i64::from(x)), ("Y", i64::from(y))];
+    if let Some(z) = height {
+        expected.push(("Z", i64::from(z)));
+    }
+
+    let mut messages = Vec::new();
+    for (axis, (name, extent)) in expected.into_iter().enumerate() {
+        for (sign, edge) in [("-", -min[axis]), ("+", max[axis])] {
+            let difference = edge - extent;
+            if difference > 1 {
+                messages.push(format!(
+                    "overhangs {}{} by {} units",
+                    name, sign, difference
+                ));
+            } else if difference < -1 {
+                messages.push(format!(
+                    "falls {} units short of {}{}",
+                    -difference, name, sign
+                ));
+            }
+        }
+    }
+    messages
+}
@@ -1,63 +1,853 @@
-use bls2brs::{bl_save, brs, convert};
+use bls2brs::{
+    archive::{self, Compression},
+    assets, bl_save, brs,
+    checkpoint::Checkpoint,
+    color::Quantize,
+    colorset,
+    config::Config,
+    convert_with_options, coverage, format, gltf, json, lint,
+    owners::OwnerMap,
+    pipeline::Pipeline,
+    preview, serve, stats, AnimatedFx, ColorSpaceMode, ConvertOptions, ConvertReport, MappingKind,
+    NameCoverage, Scale, Target,
+};
+use log::{error, info, warn, Level, LevelFilter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
-    fs::File,
-    ffi::OsStr,
-    io::BufReader,
+    collections::{hash_map::Entry, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{self, prelude::*, BufReader, BufWriter, IsTerminal},
+    net::TcpListener,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
+/// Every brick was converted.
+const EXIT_SUCCESS: i32 = 0;
+/// Files were converted, but some bricks could not be.
+const EXIT_INCOMPLETE: i32 = 1;
+/// Invalid arguments, or a file could not be converted at all.
+const EXIT_FAILURE: i32 = 2;
+
+/// Default for `--max-bricks`. Saves beyond this are slow to load even on strong servers.
+const DEFAULT_MAX_BRICKS: usize = 1_000_000;
+
 fn main() {
-    eprintln!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-    eprintln!();
+    init_logging();
+
+    info!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    info!("");
+
+    let code = match run() {
+        Ok(code) => code,
+        Err(e) => {
+            error!("{}", e);
+            if machine_output() {
+                println!(
+                    "{}",
+                    serde_json::json!({ "error": e, "exit_code": EXIT_FAILURE })
+                );
+            }
+            EXIT_FAILURE
+        }
+    };
 
-    if let Err(e) = run() {
-        eprintln!("{}", e);
-        eprintln!();
-        wexit::prompt_enter_to_exit(1);
+    info!("");
+
+    if prompts_allowed() {
+        wexit::prompt_enter_to_exit(code);
     }
+    std::process::exit(code);
+}
 
-    eprintln!();
-    wexit::prompt_enter_to_exit(0);
+/// Whether the user can be asked things. Scripts can't answer, and nobody sees the
+/// question with -q.
+fn prompts_allowed() -> bool {
+    !std::env::args()
+        .skip(1)
+        .any(|arg| matches!(arg.as_str(), "--no-prompt" | "-q" | "--quiet" | "--machine"))
 }
 
-fn run() -> Result<(), String> {
-    let args = parse_args()
-        .map_err(|_| String::from("Error: No bls files given. Drag them onto this program's executable file. (Not this window! This is just an error message, not the program itself.)"))?;
+/// Whether stdout is only for the JSON document of --machine, with everything else on
+/// stderr.
+fn machine_output() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--machine")
+}
 
-    for (i, input_path) in args.input_paths.iter().enumerate() {
-        if i > 0 {
-            println!();
-        }
+/// Sets up logging before the other arguments are parsed, so that parsing can log.
+/// `RUST_LOG` overrides the flags.
+fn init_logging() {
+    let mut level = LevelFilter::Info;
 
-        let input_path = PathBuf::from(input_path);
+    for arg in std::env::args().skip(1) {
+        level = match arg.as_str() {
+            "-q" | "--quiet" => LevelFilter::Warn,
+            "-v" => LevelFilter::Debug,
+            "-vv" => LevelFilter::Trace,
+            _ => continue,
+        };
+    }
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_env("RUST_LOG")
+        .target(if machine_output() {
+            env_logger::Target::Stderr
+        } else {
+            env_logger::Target::Stdout
+        })
+        .format(|buf, record| match record.level() {
+            Level::Info => writeln!(buf, "{}", record.args()),
+            // Keep problems out of the output of scripts that read stdout
+            level @ (Level::Error | Level::Warn) => {
+                eprintln!("{}: {}", level, record.args());
+                Ok(())
+            }
+            level => writeln!(buf, "{}: {}", level, record.args()),
+        })
+        .init();
+}
+
+/// Returns the exit code, `EXIT_SUCCESS` or `EXIT_INCOMPLETE`, or `EXIT_FAILURE` with
+/// --machine, which prints failures in its output instead.
+fn run() -> Result<i32, String> {
+    let args = parse_args()?;
+
+    match args.command {
+        Command::Convert => {}
+        Command::Colorset => return run_colorset(&args).map(|()| EXIT_SUCCESS),
+        Command::FromJson => return run_from_json(&args).map(|()| EXIT_SUCCESS),
+        Command::Coverage => return run_coverage(&args).map(|()| EXIT_SUCCESS),
+        Command::Stats => return run_stats(&args).map(|()| EXIT_SUCCESS),
+        Command::Lint => return Ok(run_lint()),
+    }
 
-        println!("Converting {}", input_path.display());
+    if let Some(output_dir) = &args.output_dir {
+        errmsg(
+            fs::create_dir_all(output_dir),
+            "Failed to create output directory",
+        )?;
+    }
 
-        if input_path.extension() != Some(OsStr::new("bls")) {
-            println!("Extension is not .bls, skipping");
+    if args.serve.is_some() {
+        return run_serve(args).map(|()| EXIT_SUCCESS);
+    }
+
+    if args.watch {
+        return run_watch(args).map(|()| EXIT_SUCCESS);
+    }
+
+    let saves = expand_inputs(&args.input_paths);
+    // An archive can hold several saves, which would share the directory
+    if args.options.checkpoint.is_some() && saves.len() > 1 {
+        return Err(format!(
+            "Error: --checkpoint-dir works on one save at a time, but the input holds {} saves",
+            saves.len()
+        ));
+    }
+    let manifest_path = &args.manifest_path;
+    // For --machine and --manifest, by index in `saves`
+    let entries = Mutex::new(vec![None; saves.len()]);
+    let duplicate_of = if args.skip_duplicate_saves {
+        find_duplicates(&saves)
+    } else {
+        vec![None; saves.len()]
+    };
+
+    // Decided before converting, so that questions don't get mixed into the output
+    let mut inputs = Vec::with_capacity(saves.len());
+    for (index, input) in saves.iter().enumerate() {
+        if let Some(original) = duplicate_of[index] {
+            info!("Skipping {}, it is a copy of {}", input, saves[original]);
             continue;
         }
 
-        let mut output_path = input_path.clone();
+        match choose_output_path(&input.path, &args)? {
+            Some(output_path) => inputs.push((index, input, output_path)),
+            None => {
+                entries.lock().unwrap()[index] =
+                    Some(serde_json::json!({ "input": input.to_string(), "skipped": true }));
+            }
+        }
+    }
 
-        output_path.set_extension("brs");
+    for (index, original) in duplicate_of.iter().enumerate() {
+        if let Some(original) = *original {
+            let mut entry = serde_json::json!({
+                "input": saves[index].to_string(),
+                "duplicate_of": saves[original].to_string(),
+            });
+            if let Some((_, _, output_path)) = inputs.iter().find(|(i, _, _)| *i == original) {
+                entry["output"] = serde_json::json!(output_path);
+            }
+            entries.lock().unwrap()[index] = Some(entry);
+        }
+    }
+
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, inputs.len().max(1));
 
-        convert_one(&input_path, &output_path)
-            .map_err(|e| format!("Error converting {}: {}", input_path.display(), e))?;
+    let next_input = AtomicUsize::new(0);
+    // Whether nothing has been printed yet, held while printing one input's output
+    let print_lock = Mutex::new(true);
+    let count_converted = AtomicUsize::new(0);
+    let count_failed = AtomicUsize::new(0);
+    let count_incomplete = AtomicUsize::new(0);
+    let count_bricks = AtomicUsize::new(0);
+    let count_cached = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                while let Some((index, input, output_path)) =
+                    inputs.get(next_input.fetch_add(1, Ordering::Relaxed))
+                {
+                    let mut out = Vec::new();
+                    let start = Instant::now();
+                    let result = convert_input(input, output_path, saves.len(), &args, &mut out);
+
+                    // Don't leave behind the empty file reserved for a renamed output
+                    if result.is_err() && *output_path != default_output_path(&input.path, &args) {
+                        let _ = fs::remove_file(output_path);
+                    }
+
+                    if args.machine || manifest_path.is_some() {
+                        entries.lock().unwrap()[*index] =
+                            Some(entry(input, output_path, &result, start.elapsed()));
+                    }
+
+                    match &result {
+                        Ok(Some(written)) => {
+                            count_converted.fetch_add(1, Ordering::Relaxed);
+                            count_bricks.fetch_add(written.bricks, Ordering::Relaxed);
+                            if written.cached {
+                                count_cached.fetch_add(1, Ordering::Relaxed);
+                            }
+                            if written.count_failure > 0 {
+                                count_incomplete.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(_) => {
+                            count_failed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+
+                    let mut first = print_lock.lock().unwrap();
+                    if !*first {
+                        info!("");
+                    }
+                    *first = false;
+                    info!("{}", out.join("\n"));
+                    if let Err(e) = result {
+                        error!("Error converting {}: {}", input, e);
+                    }
+                }
+            });
+        }
+    });
+
+    let count_failed = count_failed.into_inner();
+
+    if saves.len() > 1 {
+        let count_cached = count_cached.into_inner();
+        let count_duplicates = duplicate_of.iter().flatten().count();
+        info!("");
+        info!(
+            "Converted {} of {} files to {} bricks{}{}",
+            count_converted.into_inner(),
+            saves.len(),
+            count_bricks.into_inner(),
+            if count_cached > 0 {
+                format!(", {} of them cached", count_cached)
+            } else {
+                String::new()
+            },
+            if count_duplicates > 0 {
+                format!(", skipping {} copies", count_duplicates)
+            } else {
+                String::new()
+            }
+        );
+    }
+
+    let code = if count_failed > 0 {
+        EXIT_FAILURE
+    } else if count_incomplete.into_inner() > 0 {
+        EXIT_INCOMPLETE
+    } else {
+        EXIT_SUCCESS
+    };
+
+    let files: Vec<_> = entries
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if let Some(manifest_path) = manifest_path {
+        let manifest_file = errmsg(File::create(manifest_path), "Failed to create manifest")?;
+        errmsg(
+            serde_json::to_writer_pretty(
+                BufWriter::new(manifest_file),
+                &serde_json::json!({ "files": files }),
+            ),
+            "Failed to write manifest",
+        )?;
+        info!("Wrote manifest to {}", manifest_path.display());
+    }
+
+    if args.machine {
+        if count_failed > 0 {
+            error!("{} files failed to convert", count_failed);
+        }
+        println!(
+            "{}",
+            serde_json::json!({ "files": files, "exit_code": code })
+        );
+        return Ok(code);
+    }
+
+    if count_failed > 0 {
+        return Err(format!("{} files failed to convert", count_failed));
+    }
+
+    Ok(code)
+}
+
+/// One input's entry in the `--machine` output and the manifest.
+fn entry(
+    input: &Input,
+    output_path: &Path,
+    result: &Result<Option<Written>, String>,
+    duration: Duration,
+) -> serde_json::Value {
+    let mut entry = serde_json::json!({
+        "input": input.to_string(),
+        "duration_secs": duration.as_secs_f64(),
+    });
+    match result {
+        Ok(Some(written)) => {
+            entry["output"] = serde_json::json!(output_path);
+            entry["bricks"] = serde_json::json!(written.bricks);
+            entry["unknown_bricks"] = serde_json::json!(written.count_unknown);
+            entry["cached"] = serde_json::json!(written.cached);
+            entry["report"] = written.report.clone();
+            entry["warnings"] = serde_json::json!(written.warnings);
+        }
+        Ok(None) => entry["skipped"] = serde_json::json!(true),
+        Err(e) => entry["error"] = serde_json::json!(e),
     }
+    entry
+}
 
+/// Answers HTTP conversion requests on `Args::serve`, see `serve`. Runs until interrupted.
+fn run_serve(args: Args) -> Result<(), String> {
+    let address = args.serve.as_deref().unwrap_or_default();
+    let listener = errmsg(
+        TcpListener::bind(address),
+        &format!("Failed to listen on {}", address),
+    )?;
+    info!(
+        "Listening on http://{}, stop with Ctrl+C",
+        errmsg(listener.local_addr(), "Failed to listen")?
+    );
+    let max_bricks = args.max_bricks.filter(|_| !args.force);
+    serve::serve(listener, args.options, max_bricks);
     Ok(())
 }
 
-fn convert_one(input_path: impl AsRef<Path>, output_path: impl AsRef<Path>) -> Result<(), String> {
-    let input_path = input_path.as_ref();
+/// Converts the inputs one at a time whenever they, the config or the owners file change,
+/// printing how each report differs from the last. Runs until interrupted.
+fn run_watch(mut args: Args) -> Result<(), String> {
+    let mut reports: HashMap<String, Vec<String>> = HashMap::new();
+    let mut last_modified = Vec::new();
+
+    loop {
+        let modified: Vec<_> = args
+            .input_paths
+            .iter()
+            .map(PathBuf::from)
+            .chain(args.watch_paths.iter().cloned())
+            .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .collect();
+
+        if modified == last_modified {
+            thread::sleep(Duration::from_millis(500));
+            continue;
+        }
+
+        if !last_modified.is_empty() {
+            info!("");
+            info!("Change detected, converting again");
+            // Pick up changes to the config and owners files
+            args = match parse_args() {
+                Ok(new_args) => new_args,
+                Err(e) => {
+                    error!("{}", e);
+                    last_modified = modified;
+                    continue;
+                }
+            };
+        }
+        last_modified = modified;
+
+        let saves = expand_inputs(&args.input_paths);
+        for input in &saves {
+            // Watching regenerates its own output, so it always overwrites
+            let output_path = default_output_path(&input.path, &args);
+            let mut out = Vec::new();
+            if let Err(e) = convert_input(input, &output_path, saves.len(), &args, &mut out) {
+                out.push(format!("Error: {}", e));
+            }
+
+            let name = input.to_string();
+            info!("");
+            match reports.get(&name) {
+                None => info!("{}", out.join("\n")),
+                Some(last) if *last == out => {
+                    info!("{}: no changes", name);
+                }
+                Some(last) => {
+                    info!("{}:", name);
+                    for line in last.iter().filter(|line| !out.contains(line)) {
+                        info!("- {}", line);
+                    }
+                    for line in out.iter().filter(|line| !last.contains(line)) {
+                        info!("+ {}", line);
+                    }
+                }
+            }
+
+            reports.insert(name, out);
+        }
+    }
+}
+
+/// What was written for one input.
+#[derive(Serialize, Deserialize)]
+struct Written {
+    bricks: usize,
+    /// Source bricks that could not be converted.
+    count_failure: usize,
+    /// Source bricks with names that aren't mapped.
+    count_unknown: usize,
+    /// `ConvertReport::to_json`, for --machine.
+    report: serde_json::Value,
+    /// `ConvertReport::warnings`, one short sentence each, for --machine.
+    warnings: Vec<String>,
+    /// Copied from `Args::cache_dir` instead of converted.
+    #[serde(skip)]
+    cached: bool,
+}
+
+/// A save to convert, which may be compressed.
+struct Input {
+    /// The file to read.
+    file: PathBuf,
+    /// Where the save would be if it was not compressed, for naming outputs.
+    path: PathBuf,
+    /// The name of the save in `file`, if that is a zip archive.
+    zip_entry: Option<String>,
+}
+
+impl Input {
+    fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        let mut reader = BufReader::new(File::open(&self.file)?);
+
+        if let Some(entry) = &self.zip_entry {
+            let contents = archive::read_zip_entry(reader, entry)?;
+            return Ok(Box::new(io::Cursor::new(contents)));
+        }
+
+        Ok(match archive::detect(&mut reader)? {
+            Compression::Gzip => Box::new(archive::gunzip(reader)),
+            Compression::None | Compression::Zip => Box::new(reader),
+        })
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.zip_entry {
+            Some(entry) => write!(f, "{}: {}", self.file.display(), entry),
+            None => write!(f, "{}", self.file.display()),
+        }
+    }
+}
+
+/// Finds the saves in the files given on the command line. A gzipped `foo.bls.gz` is
+/// named like `foo.bls`, and `saves.zip` stands for a `saves` directory of its saves.
+fn expand_inputs(input_paths: &[String]) -> Vec<Input> {
+    let mut inputs = Vec::with_capacity(input_paths.len());
+
+    for input_path in input_paths {
+        let file = PathBuf::from(input_path);
+
+        // Files that can't be read are left for conversion to report
+        let compression = File::open(&file)
+            .and_then(|f| archive::detect(&mut BufReader::new(f)))
+            .unwrap_or(Compression::None);
+
+        match compression {
+            Compression::None => inputs.push(Input {
+                path: file.clone(),
+                file,
+                zip_entry: None,
+            }),
+            Compression::Gzip => {
+                let path = match file.extension() {
+                    Some(extension) if extension.eq_ignore_ascii_case("gz") => {
+                        file.with_extension("")
+                    }
+                    _ => file.clone(),
+                };
+                inputs.push(Input {
+                    path,
+                    file,
+                    zip_entry: None,
+                });
+            }
+            Compression::Zip => {
+                let entries = File::open(&file)
+                    .and_then(|f| archive::zip_saves(BufReader::new(f)))
+                    .unwrap_or_else(|e| {
+                        error!("Failed to read zip archive {}: {}", file.display(), e);
+                        Vec::new()
+                    });
+                let dir = file.with_extension("");
+                inputs.extend(entries.into_iter().map(|entry| Input {
+                    path: dir.join(&entry),
+                    file: file.clone(),
+                    zip_entry: Some(entry),
+                }));
+            }
+        }
+    }
+
+    inputs
+}
+
+/// For each save, the index of an earlier save with the same `format::fingerprint`, if
+/// there is one. Saves that can't be read are left for conversion to report.
+fn find_duplicates(saves: &[Input]) -> Vec<Option<usize>> {
+    let mut originals = HashMap::new();
+
+    saves
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let fingerprint = input.open().and_then(format::fingerprint).ok()?;
+            match originals.entry(fingerprint) {
+                Entry::Occupied(original) => Some(*original.get()),
+                Entry::Vacant(vacant) => {
+                    vacant.insert(index);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Converts one save, returning what was written, or `None` if it was skipped.
+/// `input_count` is the number of saves being converted.
+fn convert_input(
+    input: &Input,
+    output_path: &Path,
+    input_count: usize,
+    args: &Args,
+    out: &mut Vec<String>,
+) -> Result<Option<Written>, String> {
+    out.push(format!("Converting {}", input));
+
+    if !input
+        .path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("bls"))
+    {
+        out.push(String::from("Extension is not .bls, skipping"));
+        return Ok(None);
+    }
+
+    if let Some(output_dir) = output_path.parent() {
+        errmsg(
+            fs::create_dir_all(output_dir),
+            "Failed to create output directory",
+        )?;
+    }
+
+    let extra_outputs = ExtraOutputs {
+        preview: args
+            .preview_path
+            .as_ref()
+            .map(|path| extra_output_path(path, &input.path, input_count)),
+        palette: args
+            .palette_path
+            .as_ref()
+            .map(|path| extra_output_path(path, &input.path, input_count)),
+        gltf: args
+            .gltf_path
+            .as_ref()
+            .map(|path| extra_output_path(path, &input.path, input_count)),
+    };
+
+    // Only the converted save is cached, so other outputs need a conversion
+    let cache_paths = match &args.cache_dir {
+        Some(cache_dir)
+            if !extra_outputs.any()
+                && !args.options.detect_overlaps
+                && !args.options.provenance
+                && !args.write_names =>
+        {
+            let key = errmsg(cache_key(input, args), "Failed to read input")?;
+            let extension = output_path
+                .extension()
+                .unwrap_or_default()
+                .to_string_lossy();
+            Some((
+                cache_dir.join(format!("{}.{}", key, extension)),
+                cache_dir.join(format!("{}.meta.json", key)),
+            ))
+        }
+        _ => None,
+    };
+
+    if let Some((cached_save, cached_meta)) = &cache_paths {
+        if let Some(written) = read_cache(cached_save, cached_meta, output_path) {
+            out.push(format!(
+                "Cached, copied {} bricks to {}",
+                written.bricks,
+                output_path.display()
+            ));
+            return Ok(Some(written));
+        }
+    }
+
+    let written = convert_one(input, output_path, &extra_outputs, args, out)?;
+
+    if let Some((cached_save, cached_meta)) = &cache_paths {
+        if let Err(e) = write_cache(cached_save, cached_meta, output_path, &written) {
+            warn!("Failed to cache {}: {}", input, e);
+        }
+    }
+
+    Ok(Some(written))
+}
+
+/// The name of `input`'s files in `Args::cache_dir`, a hash of the input and its name, the
+/// options and author, the config and owners files, and the bls2brs version. Changing any
+/// of them converts the input again.
+fn cache_key(input: &Input, args: &Args) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    for arg in &args.option_args {
+        hasher.update([0]);
+        hasher.update(arg);
+    }
+    for path in &args.watch_paths {
+        hasher.update([0]);
+        hasher.update(fs::read(path)?);
+    }
+    // Defaults to the user running bls2brs, so not always in the options
+    hasher.update([0]);
+    hasher.update(&args.options.author.name);
+    hasher.update(args.options.author.id.as_bytes());
+    hasher.update([0]);
+    hasher.update(input.zip_entry.as_deref().unwrap_or_default());
+    // Written into the description
+    hasher.update([0]);
+    hasher.update(
+        input
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .as_encoded_bytes(),
+    );
+    hasher.update([0]);
+    io::copy(&mut File::open(&input.file)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copies a cached save to `output_path`. `None` if it isn't cached.
+fn read_cache(cached_save: &Path, cached_meta: &Path, output_path: &Path) -> Option<Written> {
+    let meta = fs::read(cached_meta).ok()?;
+    let mut written: Written = serde_json::from_slice(&meta).ok()?;
+    fs::copy(cached_save, output_path).ok()?;
+    written.cached = true;
+    Some(written)
+}
+
+fn write_cache(
+    cached_save: &Path,
+    cached_meta: &Path,
+    output_path: &Path,
+    written: &Written,
+) -> io::Result<()> {
+    if let Some(cache_dir) = cached_save.parent() {
+        fs::create_dir_all(cache_dir)?;
+    }
+    fs::copy(output_path, cached_save)?;
+    // Written last, so that an interrupted copy isn't used
+    fs::write(cached_meta, serde_json::to_vec(written)?)
+}
+
+/// Where the converted file for `input_path` goes, ignoring existing files.
+fn default_output_path(input_path: &Path, args: &Args) -> PathBuf {
+    let mut output_path = match &args.output_dir {
+        Some(output_dir) => output_dir.join(input_path.file_name().unwrap_or_default()),
+        None => input_path.to_path_buf(),
+    };
+
+    output_path.set_extension(match args.output_format {
+        OutputFormat::Brs => "brs",
+        OutputFormat::Json => "json",
+    });
+
+    output_path
+}
+
+/// Where the converted file for `input_path` goes, following `Args::existing` if there
+/// is already a file there. `None` to skip the input.
+fn choose_output_path(input_path: &Path, args: &Args) -> Result<Option<PathBuf>, String> {
+    let output_path = default_output_path(input_path, args);
+    let is_bls = input_path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("bls"));
+    if !is_bls || !output_path.exists() {
+        return Ok(Some(output_path));
+    }
+
+    let mut existing = args.existing;
+    while existing == Existing::Ask {
+        print!(
+            "{} already exists. Overwrite it? [y]es, [n]o, [r]ename: ",
+            output_path.display()
+        );
+        errmsg(io::stdout().flush(), "Failed to ask about existing file")?;
+
+        let mut answer = String::new();
+        let read = errmsg(
+            io::stdin().read_line(&mut answer),
+            "Failed to ask about existing file",
+        )?;
+
+        existing = match answer.trim().to_lowercase().as_str() {
+            // Nobody to answer, so keep the file
+            _ if read == 0 => Existing::Skip,
+            "y" | "yes" => Existing::Overwrite,
+            "n" | "no" => Existing::Skip,
+            "r" | "rename" => Existing::Rename,
+            _ => Existing::Ask,
+        };
+    }
+
+    Ok(match existing {
+        Existing::Overwrite | Existing::Ask => Some(output_path),
+        Existing::Skip => {
+            info!(
+                "Skipping {}, {} already exists",
+                input_path.display(),
+                output_path.display()
+            );
+            None
+        }
+        Existing::Rename => Some(errmsg(
+            numbered_path(&output_path),
+            "Failed to pick a new output file name",
+        )?),
+    })
+}
+
+/// The first of `foo (2).brs`, `foo (3).brs`... that doesn't exist yet, created empty so
+/// that no other input gets the same name before this one is written.
+fn numbered_path(path: &Path) -> io::Result<PathBuf> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+    for n in 2.. {
+        let numbered = path.with_file_name(format!("{} ({}).{}", stem, n, extension));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&numbered)
+        {
+            Ok(_) => return Ok(numbered),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!()
+}
+
+/// Outputs other than the brs file, for one input.
+struct ExtraOutputs {
+    preview: Option<PathBuf>,
+    palette: Option<PathBuf>,
+    gltf: Option<PathBuf>,
+}
+
+impl ExtraOutputs {
+    fn any(&self) -> bool {
+        self.preview.is_some() || self.palette.is_some() || self.gltf.is_some()
+    }
+}
+
+/// With several inputs, suffixes the requested output file name with each input's name.
+fn extra_output_path(path: &Path, input_path: &Path, input_count: usize) -> PathBuf {
+    if input_count == 1 {
+        return path.to_path_buf();
+    }
+
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push("-");
+    file_name.push(input_path.file_stem().unwrap_or_default());
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    path.with_file_name(file_name)
+}
+
+fn convert_one(
+    input: &Input,
+    output_path: impl AsRef<Path>,
+    extra_outputs: &ExtraOutputs,
+    args: &Args,
+    out: &mut Vec<String>,
+) -> Result<Written, String> {
+    let options = &args.options;
+    let input_path = &input.path;
     let output_path = output_path.as_ref();
 
-    let input_file = errmsg(File::open(input_path), "Failed to open bls file")?;
-    let input_file = BufReader::new(input_file);
+    let mut input_file = errmsg(input.open(), "Failed to open bls file")?;
+    let format = errmsg(format::detect(&mut input_file), "Failed to read bls file")?;
     let input_reader = errmsg(bl_save::Reader::new(input_file), "Failed to read bls file")?;
 
-    let mut converted = errmsg(convert(input_reader), "Failed to convert bls file")?;
+    let mut converted = match convert_with_options(input_reader, options) {
+        Ok(converted) => converted,
+        Err(e) if e.kind() == io::ErrorKind::OutOfMemory => {
+            return Err(format!(
+                "Failed to convert bls file: {}. Raise --max-memory, or leave bricks out with --skip-water or --only-owner",
+                e
+            ));
+        }
+        Err(e) => return Err(format!("Failed to convert bls file: {}", e)),
+    };
+    converted.format = format;
+    if let Some(format) = format {
+        log::debug!("{}: save with {}", input, format);
+    }
+
+    preflight(&converted, args)?;
 
     if let Some(file_name) = input_path.file_name() {
         let mut prefix = format!(
@@ -72,56 +862,863 @@ fn convert_one(input_path: impl AsRef<Path>, output_path: impl AsRef<Path>) -> R
         converted.write_data.description.insert_str(0, &prefix);
     }
 
-    if !converted.unknown_ui_names.is_empty() {
-        println!("Unknown bricks:");
-        let mut ui_names: Vec<_> = converted.unknown_ui_names.into_iter().collect();
-        ui_names.sort_by(|(_, ac), (_, bc)| ac.cmp(bc).reverse());
+    if args.description_summary {
+        let summary = converted.summary();
+        let description = &mut converted.write_data.description;
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str(&summary);
+    }
+
+    let report = if log::log_enabled!(Level::Debug) {
+        format!("{:#}", converted)
+    } else {
+        converted.to_string()
+    };
+    out.extend(report.lines().map(String::from));
+
+    if options.detect_overlaps {
+        let overlaps_path = output_path.with_extension("overlaps.txt");
+        out.push(format!(
+            "{} overlapping brick pairs, written to {}",
+            converted.overlaps.len(),
+            overlaps_path.display()
+        ));
+        errmsg(
+            write_overlaps(&overlaps_path, &converted),
+            "Failed to write overlap report",
+        )?;
+    }
+
+    if options.provenance {
+        let provenance_path = output_path.with_extension("provenance.json");
+        let provenance_file = errmsg(
+            File::create(&provenance_path),
+            "Failed to create provenance file",
+        )?;
+        errmsg(
+            serde_json::to_writer(BufWriter::new(provenance_file), &converted.provenance),
+            "Failed to write provenance",
+        )?;
+        out.push(format!(
+            "Wrote brick provenance to {}",
+            provenance_path.display()
+        ));
+    }
+
+    if args.write_names && !converted.named_bricks.is_empty() {
+        let names_path = output_path.with_extension("names.txt");
+        errmsg(
+            write_names(&names_path, &converted),
+            "Failed to write brick names",
+        )?;
+        out.push(format!(
+            "Wrote {} brick names to {}",
+            converted.named_bricks.len(),
+            names_path.display()
+        ));
+    }
+
+    if let Some(preview_path) = &extra_outputs.preview {
+        let preview_file = errmsg(File::create(preview_path), "Failed to create preview file")?;
+        errmsg(
+            preview::write_png(BufWriter::new(preview_file), &converted.write_data, 2048),
+            "Failed to write preview",
+        )?;
+        out.push(format!("Wrote preview to {}", preview_path.display()));
+    }
+
+    if let Some(palette_path) = &extra_outputs.palette {
+        let palette_file = errmsg(
+            File::create(palette_path),
+            "Failed to create palette preview file",
+        )?;
+        errmsg(
+            preview::write_palette_png(
+                BufWriter::new(palette_file),
+                &converted.source_colors,
+                &converted.write_data.colors,
+                16,
+            ),
+            "Failed to write palette preview",
+        )?;
+        out.push(format!(
+            "Wrote palette preview to {}",
+            palette_path.display()
+        ));
+    }
+
+    if let Some(gltf_path) = &extra_outputs.gltf {
+        let gltf_file = errmsg(File::create(gltf_path), "Failed to create glTF file")?;
+        errmsg(
+            gltf::write_glb(BufWriter::new(gltf_file), &converted.write_data),
+            "Failed to write glTF file",
+        )?;
+        out.push(format!("Wrote glTF model to {}", gltf_path.display()));
+    }
+
+    match args.output_format {
+        OutputFormat::Brs => {
+            let mut output_file = errmsg(File::create(output_path), "Failed to create BRS file")?;
+
+            errmsg(
+                brs::write_save(&mut output_file, &converted.write_data),
+                "Failed to write BRS file",
+            )?;
+        }
+        OutputFormat::Json => {
+            let output_file = errmsg(File::create(output_path), "Failed to create JSON file")?;
+
+            errmsg(
+                json::write_json(BufWriter::new(output_file), &converted.write_data),
+                "Failed to write JSON file",
+            )?;
+        }
+    }
+
+    if let Some(checkpoint) = &options.checkpoint {
+        if let Err(e) = checkpoint.clear() {
+            warn!(
+                "Failed to remove the checkpoint in {}: {}",
+                checkpoint.dir.display(),
+                e
+            );
+        }
+    }
+
+    Ok(Written {
+        bricks: converted.write_data.bricks.len(),
+        count_failure: converted.count_failure,
+        count_unknown: converted.unknown_ui_names.values().sum(),
+        report: converted.to_json(),
+        warnings: converted.warnings.iter().map(ToString::to_string).collect(),
+        cached: false,
+    })
+}
+
+fn run_from_json(args: &Args) -> Result<(), String> {
+    for input_path in &args.input_paths {
+        let input_path = PathBuf::from(input_path);
+        let mut output_path = input_path.clone();
+        output_path.set_extension("brs");
+
+        info!("Converting {}", input_path.display());
+
+        let input_file = errmsg(File::open(&input_path), "Failed to open JSON file")?;
+        let data = errmsg(
+            json::read_json(BufReader::new(input_file)),
+            "Failed to read JSON file",
+        )?;
+
+        let mut output_file = errmsg(File::create(&output_path), "Failed to create BRS file")?;
+        errmsg(
+            brs::write_save(&mut output_file, &data),
+            "Failed to write BRS file",
+        )?;
+
+        info!(
+            "Wrote {} bricks to {}",
+            data.bricks.len(),
+            output_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn run_stats(args: &Args) -> Result<(), String> {
+    for (index, input) in expand_inputs(&args.input_paths).into_iter().enumerate() {
+        let mut input_file = errmsg(input.open(), "Failed to open input file")?;
+        errmsg(format::detect(&mut input_file), "Failed to read input file")?;
+        let reader = errmsg(
+            bl_save::Reader::new(input_file),
+            "Failed to read input file",
+        )?;
+        let stats = errmsg(stats::stats(reader), "Failed to read input file")?;
+
+        if index > 0 {
+            info!("");
+        }
+        info!("{}: {} bricks", input, stats.brick_count);
+
+        if let Some((min, max)) = stats.bounds {
+            // Blockland units are 2 studs wide and 5 plates tall
+            info!(
+                "Brick positions span {} x {} studs and {} plates, from ({}, {}, {}) to ({}, {}, {})",
+                (max[0] - min[0]) * 2.0,
+                (max[1] - min[1]) * 2.0,
+                ((max[2] - min[2]) * 5.0).round(),
+                min[0],
+                min[1],
+                min[2],
+                max[0],
+                max[1],
+                max[2],
+            );
+        }
+
+        let mut ui_names: Vec<_> = stats.ui_names.iter().collect();
+        ui_names.sort_by(|(an, ac), (bn, bc)| ac.cmp(bc).reverse().then(an.cmp(bn)));
+        info!("Bricks:");
         for (ui_name, count) in ui_names {
-            let ui_name = if ui_name != ui_name.trim() {
-                format!("{:?}", ui_name)
-            } else {
-                ui_name
+            info!("  {:<28} {:>6} bricks", ui_name, count);
+        }
+
+        let mut colors: Vec<_> = stats
+            .color_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .collect();
+        colors.sort_by(|(ai, ac), (bi, bc)| ac.cmp(bc).reverse().then(ai.cmp(bi)));
+        info!("Colors:");
+        for (color_index, count) in colors {
+            let (r, g, b, a) = stats.colors[color_index];
+            let [r, g, b, a] = [r, g, b, a].map(|c| (c * 255.0).round() as u8);
+            let color = format!("{:>2} ({}, {}, {}, {})", color_index, r, g, b, a);
+            info!("  {:<28} {:>6} bricks", color, count);
+        }
+
+        let mut owners: Vec<_> = stats.owners.iter().collect();
+        owners.sort_by(|(ao, ac), (bo, bc)| ac.cmp(bc).reverse().then(ao.cmp(bo)));
+        info!("Owners:");
+        for (owner, count) in owners {
+            let owner = match owner {
+                Some(bl_id) => format!("BL_ID {}", bl_id),
+                None => String::from("unknown"),
             };
-            println!("  {:<28} {:>4} bricks", ui_name, count);
+            info!("  {:<28} {:>6} bricks", owner, count);
         }
     }
 
-    if converted.count_failure > 0 {
-        println!("{} bricks failed to convert", converted.count_failure);
+    Ok(())
+}
+
+fn run_lint() -> i32 {
+    let issues = lint::lint_mappings();
+    for issue in &issues {
+        error!(
+            "{} (brick {}): {}",
+            issue.ui_name, issue.desc_index, issue.message
+        );
+    }
+
+    if issues.is_empty() {
+        info!("No problems found in the mapping tables");
+        EXIT_SUCCESS
+    } else {
+        info!("{} problems found in the mapping tables", issues.len());
+        EXIT_FAILURE
     }
+}
 
-    println!(
-        "{} of {} bricks converted successfully to {} bricks",
-        converted.count_success,
-        converted.count_success + converted.count_failure,
-        converted.write_data.bricks.len(),
-    );
+fn run_coverage(args: &Args) -> Result<(), String> {
+    let mut names: HashMap<String, NameCoverage> = HashMap::new();
 
-    let mut output_file = errmsg(File::create(output_path), "Failed to create BRS file")?;
+    for input in expand_inputs(&args.input_paths) {
+        let mut input_file = errmsg(input.open(), "Failed to open input file")?;
+        errmsg(format::detect(&mut input_file), "Failed to read input file")?;
+        let reader = errmsg(
+            bl_save::Reader::new(input_file),
+            "Failed to read input file",
+        )?;
+        let file_names = errmsg(coverage(reader), "Failed to read input file")?;
 
-    errmsg(
-        brs::write_save(&mut output_file, &converted.write_data),
-        "Failed to write BRS file",
-    )?;
+        let total: usize = file_names.values().map(|name| name.count).sum();
+        let unmapped: usize = file_names
+            .values()
+            .filter(|name| name.kind == MappingKind::Unmapped)
+            .map(|name| name.count)
+            .sum();
+        info!(
+            "{}: {} of {} bricks can be converted",
+            input,
+            total - unmapped,
+            total
+        );
+
+        for (ui_name, file_name) in file_names {
+            let name = names.entry(ui_name).or_insert(NameCoverage {
+                kind: file_name.kind,
+                count: 0,
+            });
+            name.kind = name.kind.max(file_name.kind);
+            name.count += file_name.count;
+        }
+    }
+
+    let total: usize = names.values().map(|name| name.count).sum();
+    let mut names: Vec<_> = names.into_iter().collect();
+    names.sort_by(|(an, a), (bn, b)| {
+        a.kind
+            .cmp(&b.kind)
+            .then(a.count.cmp(&b.count).reverse())
+            .then(an.cmp(bn))
+    });
+
+    for (kind, label) in [
+        (MappingKind::Exact, "Exact"),
+        (MappingKind::Approximate, "Approximate"),
+        (MappingKind::Regex, "Mapped by pattern"),
+        (MappingKind::Unmapped, "Unmapped"),
+    ] {
+        let kind_names: Vec<_> = names.iter().filter(|(_, name)| name.kind == kind).collect();
+        if kind_names.is_empty() {
+            continue;
+        }
+
+        let count: usize = kind_names.iter().map(|(_, name)| name.count).sum();
+        info!("");
+        info!(
+            "{}: {} bricks ({:.1}%) with {} names",
+            label,
+            count,
+            count as f64 * 100.0 / total as f64,
+            kind_names.len()
+        );
+
+        // The names that will convert as they are only matter when debugging mappings
+        for (ui_name, name) in kind_names {
+            match kind {
+                MappingKind::Exact | MappingKind::Regex => {
+                    log::debug!("  {:?} x{}", ui_name, name.count)
+                }
+                MappingKind::Approximate | MappingKind::Unmapped => {
+                    info!("  {:?} x{}", ui_name, name.count)
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_names(path: &Path, converted: &ConvertReport) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+
+    for named in &converted.named_bricks {
+        let (x, y, z) = named.position;
+        writeln!(
+            w,
+            "{} at ({}, {}, {}): {}",
+            named.name, x, y, z, named.ui_name
+        )?;
+    }
+
+    w.flush()
+}
+
+fn write_overlaps(path: &Path, converted: &ConvertReport) -> io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    let data = &converted.write_data;
+
+    for overlap in &converted.overlaps {
+        let (a, b) = overlap.bricks;
+        let ((ax, ay, az), (bx, by, bz)) = overlap.positions;
+        writeln!(
+            w,
+            "{} at ({}, {}, {}) overlaps {} at ({}, {}, {})",
+            data.brick_assets[data.bricks[a].asset_name_index as usize],
+            ax,
+            ay,
+            az,
+            data.brick_assets[data.bricks[b].asset_name_index as usize],
+            bx,
+            by,
+            bz,
+        )?;
+    }
+
+    w.flush()
+}
+
+fn run_colorset(args: &Args) -> Result<(), String> {
+    for input_path in &args.input_paths {
+        let input_path = PathBuf::from(input_path);
+        let mut output_path = input_path.clone();
+        output_path.set_extension("json");
+
+        info!("Converting colorset {}", input_path.display());
+
+        let input_file = errmsg(File::open(&input_path), "Failed to open colorset file")?;
+        let groups = errmsg(
+            colorset::read_colorset(BufReader::new(input_file), ColorSpaceMode::Srgb),
+            "Failed to read colorset file",
+        )?;
+
+        let output_file = errmsg(File::create(&output_path), "Failed to create palette file")?;
+        errmsg(
+            colorset::write_palette_preset(output_file, &groups),
+            "Failed to write palette file",
+        )?;
+
+        info!(
+            "Wrote {} colors in {} groups to {}",
+            groups.iter().map(|g| g.colors.len()).sum::<usize>(),
+            groups.len(),
+            output_path.display()
+        );
+    }
 
     Ok(())
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Convert,
+    Colorset,
+    FromJson,
+    Coverage,
+    Stats,
+    /// Check the mapping tables, for contributors.
+    Lint,
+}
+
+/// What to do when a converted file would replace an existing one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Existing {
+    Overwrite,
+    Skip,
+    /// Write `foo (2).brs` instead.
+    Rename,
+    /// Ask which of the others to do.
+    Ask,
+}
+
+enum OutputFormat {
+    Brs,
+    Json,
+}
+
 struct Args {
+    command: Command,
     input_paths: Vec<String>,
+    /// Number of files to convert at once, by default one per CPU.
+    jobs: Option<usize>,
+    output_dir: Option<PathBuf>,
+    preview_path: Option<PathBuf>,
+    palette_path: Option<PathBuf>,
+    gltf_path: Option<PathBuf>,
+    output_format: OutputFormat,
+    /// Append `ConvertReport::summary` to the save description.
+    description_summary: bool,
+    existing: Existing,
+    /// Write the names of named bricks next to the output.
+    write_names: bool,
+    /// Convert again whenever an input or `watch_paths` changes.
+    watch: bool,
+    /// The config and owners files that were read, besides the inputs.
+    watch_paths: Vec<PathBuf>,
+    /// Answer conversion requests on this address instead of converting inputs.
+    serve: Option<String>,
+    /// Print one JSON document describing the conversion on stdout, see `entry`.
+    machine: bool,
+    /// Write the `entry` of each save to this file.
+    manifest_path: Option<PathBuf>,
+    /// Convert only the first of saves with the same `format::fingerprint`.
+    skip_duplicate_saves: bool,
+    /// Where converted saves are kept by `cache_key`, to copy instead of converting again.
+    cache_dir: Option<PathBuf>,
+    /// See `output_args`.
+    option_args: Vec<String>,
+    /// Refuse to write saves with more bricks than this, unless `force` is set.
+    max_bricks: Option<usize>,
+    /// Refuse to write saves estimated to be larger than this many megabytes, unless
+    /// `force` is set.
+    max_size_mb: Option<u64>,
+    force: bool,
+    options: ConvertOptions,
 }
 
-fn parse_args() -> Result<Args, ()> {
-    let mut args = std::env::args();
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().peekable();
     args.next().unwrap();
 
-    let input_paths: Vec<_> = args.collect();
+    let command = match args.peek().map(String::as_str) {
+        Some("colorset") => {
+            args.next();
+            Command::Colorset
+        }
+        Some("from-json") => {
+            args.next();
+            Command::FromJson
+        }
+        Some("coverage") => {
+            args.next();
+            Command::Coverage
+        }
+        Some("stats") => {
+            args.next();
+            Command::Stats
+        }
+        Some("lint") => {
+            args.next();
+            Command::Lint
+        }
+        _ => Command::Convert,
+    };
+
+    let mut input_paths = Vec::new();
+    let mut jobs = None;
+    let mut output_dir = None;
+    let mut preview_path = None;
+    let mut palette_path = None;
+    let mut gltf_path = None;
+    let mut output_format = OutputFormat::Brs;
+    let mut description_summary = false;
+    let mut watch = false;
+    let mut serve = None;
+    let mut machine = false;
+    let mut cache_dir = None;
+    let mut manifest_path = None;
+    let mut skip_duplicate_saves = false;
+    let mut write_names = false;
+    let can_ask = prompts_allowed() && io::stdin().is_terminal();
+    let mut existing = if can_ask {
+        Existing::Ask
+    } else {
+        Existing::Overwrite
+    };
+    let mut watch_paths = Vec::new();
+    let mut pass_specs: Option<Vec<String>> = None;
+    let mut owner_name = None;
+    let mut owner_id = None;
+    let mut max_bricks = Some(DEFAULT_MAX_BRICKS);
+    let mut max_size_mb = None;
+    let mut force = false;
+    let mut options = ConvertOptions::default();
+
+    if let Some(user_name) = std::env::var_os("USER").or_else(|| std::env::var_os("USERNAME")) {
+        options.author.name = user_name.to_string_lossy().into_owned();
+    }
+
+    if let Some((path, config)) = errmsg(Config::load(), "Error: Invalid config file")? {
+        info!("Using config {}", path.display());
+        watch_paths.push(path);
+        config.apply(&mut options);
+        output_dir = config.output_dir;
+        cache_dir = config.cache_dir;
+        description_summary = config.description_summary.unwrap_or(false);
+        owner_name = config.owner_name;
+        owner_id = config.owner_id;
+        if let Some(max) = config.max_bricks {
+            max_bricks = Some(max).filter(|&max| max > 0);
+        }
+        if let Some(max) = config.max_size_mb {
+            max_size_mb = Some(max).filter(|&max| max > 0);
+        }
+        if let Some(on_existing) = &config.on_existing {
+            existing = parse_existing(on_existing)?;
+        }
+        if let Some(format) = &config.output_format {
+            output_format = parse_output_format(format)?;
+        }
+        if let Some(owners_path) = &config.owners {
+            options.owners = read_owners(owners_path)?;
+            watch_paths.push(owners_path.clone());
+        }
+        if let Some(asset_list_path) = &config.asset_list {
+            options.known_assets = read_asset_list(asset_list_path)?;
+            watch_paths.push(asset_list_path.clone());
+        }
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Handled by `init_logging`
+            "-q" | "--quiet" | "-v" | "-vv" => {}
+            // Handled by `main`
+            "--no-prompt" => {}
+            "--machine" => machine = true,
+            "-j" | "--jobs" => {
+                let value = flag_value(&mut args, &arg)?;
+                jobs = Some(errmsg(value.parse(), "Invalid number for --jobs")?);
+            }
+            "--author" => options.author.name = flag_value(&mut args, &arg)?,
+            "--author-id" => {
+                let id = flag_value(&mut args, &arg)?;
+                options.author.id = errmsg(
+                    brs::uuid::Uuid::parse_str(&id),
+                    "Invalid UUID for --author-id",
+                )?;
+            }
+            "--owner-name" => owner_name = Some(flag_value(&mut args, &arg)?),
+            "--owner-uuid" => {
+                let id = flag_value(&mut args, &arg)?;
+                owner_id = Some(errmsg(
+                    brs::uuid::Uuid::parse_str(&id),
+                    "Invalid UUID for --owner-uuid",
+                )?);
+            }
+            "--map" => options.map = flag_value(&mut args, &arg)?,
+            "--description-summary" => description_summary = true,
+            "--dedup" => options.dedup = true,
+            "--strip-baseplates" => options.strip_baseplates = true,
+            "--add-baseplate" => options.add_baseplate = true,
+            "--overlaps" => options.detect_overlaps = true,
+            "--merge-terrain" => options.merge_terrain = true,
+            "--pass" => pass_specs
+                .get_or_insert_with(Vec::new)
+                .push(flag_value(&mut args, &arg)?),
+            "--snap" => {
+                let value = flag_value(&mut args, &arg)?;
+                options.grid_snap = Some(errmsg(value.parse(), "Invalid number for --snap")?);
+            }
+            "--provenance" => options.provenance = true,
+            "--checkpoint-dir" => {
+                let dir = PathBuf::from(flag_value(&mut args, &arg)?);
+                options.checkpoint = Some(Checkpoint::new(dir));
+            }
+            "--check-geometry" => options.check_geometry = true,
+            "--names" => write_names = true,
+            "--placeholders" => options.placeholders = true,
+            "--strict" => options.strict = true,
+            "--lenient" => options.lenient = true,
+            "--placeholder-unknown" => options.placeholder_unknown = true,
+            "--skip-invisible" => options.skip_invisible = true,
+            "--skip-noncolliding" => options.skip_noncolliding = true,
+            "--skip-water" => options.skip_water = true,
+            "--curated-colors" => options.curated_colors = true,
+            "--only-owner" => {
+                let bl_id = flag_value(&mut args, &arg)?;
+                let bl_id = errmsg(bl_id.parse(), "Invalid BL_ID for --only-owner")?;
+                options.only_owners.insert(bl_id);
+            }
+            "--scale" => {
+                options.scale = match flag_value(&mut args, &arg)?.as_str() {
+                    "normal" => Scale::Normal,
+                    "micro" => Scale::Micro,
+                    other => return Err(format!("Error: Unknown scale {}", other)),
+                }
+            }
+            "--animated-fx" => {
+                let mode = flag_value(&mut args, &arg)?;
+                options.animated_fx = AnimatedFx::parse(&mode)
+                    .ok_or_else(|| format!("Error: Unknown --animated-fx mode {}", mode))?;
+            }
+            "--target" => {
+                let target = flag_value(&mut args, &arg)?;
+                options.target = Target::parse(&target)
+                    .ok_or_else(|| format!("Error: Unknown target {}", target))?;
+            }
+            "--to" => output_format = parse_output_format(&flag_value(&mut args, &arg)?)?,
+            "--gltf" => gltf_path = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--preview" => preview_path = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--palette-preview" => palette_path = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--owners" => {
+                let owners_path = PathBuf::from(flag_value(&mut args, &arg)?);
+                options.owners = read_owners(&owners_path)?;
+                watch_paths.push(owners_path);
+            }
+            "--asset-list" => {
+                let asset_list_path = PathBuf::from(flag_value(&mut args, &arg)?);
+                options.known_assets = read_asset_list(&asset_list_path)?;
+                watch_paths.push(asset_list_path);
+            }
+            "--quantize-colors" => {
+                let colorset_path = PathBuf::from(flag_value(&mut args, &arg)?);
+                let quantize = read_quantize(&colorset_path, options.color_space)?;
+                options.color_mapper = Some(Box::new(quantize));
+                watch_paths.push(colorset_path);
+            }
+            "--watch" => watch = true,
+            "--serve" => serve = Some(flag_value(&mut args, &arg)?),
+            "--overwrite" => existing = Existing::Overwrite,
+            "--no-overwrite" => existing = Existing::Skip,
+            "--auto-rename" => existing = Existing::Rename,
+            "--force" => force = true,
+            "--max-bricks" => {
+                let value = flag_value(&mut args, &arg)?;
+                let max = errmsg(value.parse(), "Invalid number for --max-bricks")?;
+                max_bricks = Some(max).filter(|&max| max > 0);
+            }
+            "--max-size" => {
+                let value = flag_value(&mut args, &arg)?;
+                let max = errmsg(value.parse(), "Invalid number for --max-size")?;
+                max_size_mb = Some(max).filter(|&max| max > 0);
+            }
+            "--max-memory" => {
+                let value = flag_value(&mut args, &arg)?;
+                let max: u64 = errmsg(value.parse(), "Invalid number for --max-memory")?;
+                options.memory_limit = Some(max.saturating_mul(1_000_000)).filter(|&max| max > 0);
+            }
+            "--output-dir" => output_dir = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--manifest" => manifest_path = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            "--skip-duplicate-saves" => skip_duplicate_saves = true,
+            "--cache-dir" => cache_dir = Some(PathBuf::from(flag_value(&mut args, &arg)?)),
+            flag if flag.starts_with("--") => {
+                return Err(format!("Error: Unknown option {}", flag));
+            }
+            _ => input_paths.push(arg),
+        }
+    }
+
+    // Replaces the config's passes rather than adding to them, like other flags
+    if let Some(specs) = &pass_specs {
+        options.pipeline = errmsg(Pipeline::from_specs(specs), "Error: Invalid --pass")?;
+    }
+
+    match (owner_name, owner_id) {
+        (name, Some(id)) => {
+            options.owner = Some(brs::User {
+                id,
+                name: name.unwrap_or_else(|| options.author.name.clone()),
+            });
+        }
+        (Some(_), None) => {
+            return Err(String::from(
+                "Error: --owner-name needs --owner-uuid, the id of your Brickadia account",
+            ));
+        }
+        (None, None) => {}
+    }
+
+    // A question would block scripts and, with --machine, end up in the JSON document
+    if existing == Existing::Ask && !can_ask {
+        existing = Existing::Skip;
+    }
+
+    if options.checkpoint.is_some()
+        && (input_paths.len() > 1 || watch || serve.is_some() || options.provenance)
+    {
+        return Err(String::from(
+            "Error: --checkpoint-dir works on one save at a time, without --watch, --serve or --provenance",
+        ));
+    }
+
+    if machine && (command != Command::Convert || watch || serve.is_some()) {
+        return Err(String::from(
+            "Error: --machine only works when converting files once",
+        ));
+    }
+
+    if input_paths.is_empty() && command != Command::Lint && serve.is_none() {
+        return Err(String::from("Error: No bls files given. Drag them onto this program's executable file. (Not this window! This is just an error message, not the program itself.)"));
+    }
+
+    let option_args = output_args(&input_paths);
+
+    Ok(Args {
+        command,
+        input_paths,
+        jobs,
+        output_dir,
+        preview_path,
+        palette_path,
+        gltf_path,
+        output_format,
+        description_summary,
+        existing,
+        write_names,
+        watch,
+        watch_paths,
+        serve,
+        machine,
+        manifest_path,
+        skip_duplicate_saves,
+        cache_dir,
+        option_args,
+        max_bricks,
+        max_size_mb,
+        force,
+        options,
+    })
+}
+
+/// The arguments that change what is written, for `cache_key`. Inputs and flags that
+/// only change how bls2brs runs are left out.
+fn output_args(input_paths: &[String]) -> Vec<String> {
+    let mut output_args = Vec::new();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" | "-v" | "-vv" | "--no-prompt" | "--machine" => {}
+            "-j" | "--jobs" | "--checkpoint-dir" => {
+                args.next();
+            }
+            _ if input_paths.contains(&arg) => {}
+            _ => output_args.push(arg),
+        }
+    }
+
+    output_args
+}
+
+/// Checks the converted save against `Args::max_bricks` and `Args::max_size_mb`, so
+/// saves too big for a server aren't written by accident.
+fn preflight(converted: &ConvertReport, args: &Args) -> Result<(), String> {
+    if args.force {
+        return Ok(());
+    }
+
+    let mut problems = Vec::new();
+    let bricks = converted.write_data.bricks.len();
+    if let Some(max) = args.max_bricks.filter(|&max| bricks > max) {
+        problems.push(format!("{} bricks, more than --max-bricks {}", bricks, max));
+    }
+
+    let size_mb = converted.estimated_size() / 1_000_000;
+    if let Some(max) = args.max_size_mb.filter(|&max| size_mb > max) {
+        problems.push(format!(
+            "about {} MB, more than --max-size {}",
+            size_mb, max
+        ));
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "The converted save would have {}. Pass --force to write it anyway, or make it smaller with --merge-terrain, --skip-water or --only-owner",
+        problems.join(" and ")
+    ))
+}
+
+fn parse_existing(on_existing: &str) -> Result<Existing, String> {
+    match on_existing {
+        "ask" => Ok(Existing::Ask),
+        "overwrite" => Ok(Existing::Overwrite),
+        "skip" => Ok(Existing::Skip),
+        "rename" => Ok(Existing::Rename),
+        other => Err(format!("Error: Unknown on_existing {}", other)),
+    }
+}
 
-    if input_paths.is_empty() {
-        return Err(());
+fn parse_output_format(format: &str) -> Result<OutputFormat, String> {
+    match format {
+        "brs" => Ok(OutputFormat::Brs),
+        "json" => Ok(OutputFormat::Json),
+        other => Err(format!("Error: Unknown output format {}", other)),
     }
+}
+
+fn read_owners(path: &Path) -> Result<OwnerMap, String> {
+    let source = errmsg(fs::read_to_string(path), "Failed to read owners file")?;
+    errmsg(OwnerMap::from_toml(&source), "Invalid owners file")
+}
+
+fn read_asset_list(path: &Path) -> Result<Vec<String>, String> {
+    let source = errmsg(fs::read_to_string(path), "Failed to read asset list")?;
+    Ok(assets::parse_list(&source))
+}
+
+fn read_quantize(path: &Path, color_space: ColorSpaceMode) -> Result<Quantize, String> {
+    let file = errmsg(File::open(path), "Failed to open colorset file")?;
+    let groups = errmsg(
+        colorset::read_colorset(BufReader::new(file), color_space),
+        "Failed to read colorset file",
+    )?;
+    let palette = groups.into_iter().flat_map(|group| group.colors).collect();
+    Quantize::new(palette, color_space)
+        .ok_or_else(|| format!("Error: {} has no colors", path.display()))
+}
 
-    Ok(Args { input_paths })
+fn flag_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Error: Missing value for {}", flag))
 }
 
 fn errmsg<T, E: std::fmt::Display>(r: Result<T, E>, message_prefix: &str) -> Result<T, String> {
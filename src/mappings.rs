@@ -1,530 +1,718 @@
 #![allow(clippy::identity_op)]
 
-use crate::types::{BrickDesc, BrickMapping};
+use crate::{
+    types::{BrickDesc, BrickMapping, PrintFace},
+    MinigameKind,
+};
 use lazy_static::lazy_static;
-use regex::{Captures, Regex};
+use phf::phf_map;
+use regex::{Captures, Regex, RegexSet};
 use std::collections::{HashMap, HashSet};
 use brs::Direction::*;
 
 type RegexHandler = Box<dyn Fn(Captures, &bl_save::Brick) -> Option<BrickMapping> + Sync>;
 
+const BRICK_ROAD_LANE: BrickDesc = BrickDesc::new("PB_DefaultTile")
+    .color_override([51, 51, 51, 255]);
+const BRICK_ROAD_STRIPE: BrickDesc = BrickDesc::new("PB_DefaultTile")
+    .color_override([254, 254, 232, 255]);
+
+/// A wedge with a road print is a lane, with the top cut down by a plate for the markings
+/// along its diagonal edge.
+const ROAD_WEDGE_1X1: &[BrickDesc] = &[
+    BrickDesc { asset: "PB_DefaultSideWedge", ..BRICK_ROAD_LANE }.size((5, 5, 5)).offset((0, 0, -1)).rotation_offset(0),
+    BrickDesc { asset: "PB_DefaultMicroWedge", ..BRICK_ROAD_STRIPE }.size((5, 5, 1)).offset((0, 0, 5)).rotation_offset(0),
+];
+
+const ROAD_WEDGE_2X2: &[BrickDesc] = &[
+    BrickDesc { asset: "PB_DefaultSideWedge", ..BRICK_ROAD_LANE }.size((10, 10, 5)).offset((0, 0, -1)).rotation_offset(0),
+    BRICK_ROAD_LANE.size((5, 5, 1)).offset((-5, -5, 5)), // inner corner
+    BrickDesc { asset: "PB_DefaultMicroWedge", ..BRICK_ROAD_STRIPE }.size((5, 5, 1)).offset((5, -5, 5)).rotation_offset(0),
+    BrickDesc { asset: "PB_DefaultMicroWedge", ..BRICK_ROAD_STRIPE }.size((5, 5, 1)).offset((-5, 5, 5)).rotation_offset(0),
+];
+
+const GENERIC_DOOR: &[BrickDesc] = &[
+    //frame
+    BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, -35)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, 35)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, 19, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, -19, 0)),
+    //door
+    BrickDesc::new("PB_DefaultMicroBrick").size((18, 1, 34)),
+    //handle
+    BrickDesc::new("PB_DefaultMicroBrick").size((3, 1, 3)).offset((2, 12, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((3, 1, 3)).offset((-2, 12, 0)),
+];
+
+/// Teledoors and teleporters are written as portals in these colors, see `is_portal`.
+const PORTAL_FRAME_COLOR: [u8; 4] = [90, 0, 200, 255];
+const PORTAL_COLOR: [u8; 4] = [0, 200, 255, 128];
+
+/// A door frame around a see-through pane players can walk through, like the teledoor.
+const TELEDOOR: &[BrickDesc] = &[
+    //frame
+    BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, -35)).color_override(PORTAL_FRAME_COLOR),
+    BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, 35)).color_override(PORTAL_FRAME_COLOR),
+    BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, 19, 0)).color_override(PORTAL_FRAME_COLOR),
+    BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, -19, 0)).color_override(PORTAL_FRAME_COLOR),
+    //pane
+    BrickDesc::new("PB_DefaultMicroBrick").size((18, 1, 34)).color_override(PORTAL_COLOR).collision_override(false),
+];
+
+const ROUND_4X4F: &[BrickDesc] = &[
+    BrickDesc::new("PB_DefaultBrick").size((10, 10, 2)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, 14, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, -14, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, -14, 0)).rotation_offset(2),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, 14, 0)).rotation_offset(1),
+    BrickDesc::new("PB_DefaultMicroBrick").size((4, 10, 2)).offset((0, 14, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((4, 10, 2)).offset((0, -14, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((14, 0, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((-14, 0, 0)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, 19, 0)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((5, 19, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((5, -19, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, -19, 0)).rotation_offset(2),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((19, -5, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((19, 5, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-19, 5, 0)).rotation_offset(1),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-19, -5, 0)).rotation_offset(2),
+];
+
+const PRINT_4X4F_ROUND: &[BrickDesc] = &[
+    BrickDesc::new("PB_DefaultMicroBrick").size((18, 10, 2)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, 14, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, -14, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, -14, 0)).rotation_offset(2),
+    BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, 14, 0)).rotation_offset(1),
+    BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((14, 0, 0)),
+    BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((-14, 0, 0)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, 19, 0)),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((5, 19, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((5, -19, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, -19, 0)).rotation_offset(2),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((19, -5, 0)).rotation_offset(3),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((19, 5, 0)).rotation_offset(0),
+    BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-19, 5, 0)).rotation_offset(1),
+    BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-19, -5, 0)).rotation_offset(2),
+];
+
+/// Literal mappings that reproduce the Blockland brick.
+pub static BRICK_MAP_EXACT: phf::Map<&'static str, &'static [BrickDesc]> = phf_map! {
+    "1x1 Cone" => &[BrickDesc::new("B_1x1_Cone")],
+    "2x2x2 Cone" => &[BrickDesc::new("B_2x2_Cone")],
+    "1x1 cone Inv" => &[BrickDesc::new("B_1x1_Cone").direction_override(ZNegative)], // 1RandomBrickPack
+    "2x2x2 cone Inv" => &[BrickDesc::new("B_2x2_Cone").direction_override(ZNegative)], // 1RBP
+    "1x1 Round" => &[BrickDesc::new("B_1x1_Round")],
+    "1x1 Octo Plate" => &[BrickDesc::new("B_1x1F_Octo")],
+    "1x1F Round" => &[BrickDesc::new("B_1x1F_Round")],
+    "2x2 Round" => &[BrickDesc::new("B_2x2_Round")],
+    "2x2F Round" => &[BrickDesc::new("B_2x2F_Round")],
+    "1x1F Round Print" => &[BrickDesc::new("B_1x1F_Round").print_dropped(true).print_face(PrintFace::Top)],
+    "2x2F Round Print" => &[BrickDesc::new("B_2x2F_Round").print_dropped(true).print_face(PrintFace::Top)],
+    "Pine Tree" => &[BrickDesc::new("B_Pine_Tree").offset((0, 0, -6))],
+    "2x2 Corner" => &[BrickDesc::new("B_2x2_Corner").rotation_offset(0)],
+    "1x1 Wedge" => &[BrickDesc::new("PB_DefaultSideWedge").size((5, 5, 6)).rotation_offset(0)],
+    "2x2 Wedge" => &[BrickDesc::new("PB_DefaultSideWedge").size((10, 10, 6)).rotation_offset(0)],
+    "2x2 Octo Plate" => &[BrickDesc::new("B_2x2F_Octo")],
+    "1x1F Octo" => &[BrickDesc::new("B_1x1F_Octo")],
+    "2x2F Octo" => &[BrickDesc::new("B_2x2F_Octo")],
+    "8x8 Grill" => &[BrickDesc::new("B_8x8_Lattice_Plate")],
+    "1x4x2 Picket" => &[BrickDesc::new("B_Picket_Fence")],
+
+    // 1RandomBrickPack 45° to 25° Ramp Adapters
+    "45° 25° Adapter A" => &[BrickDesc::new("PB_DefaultRampInnerCorner").size((15, 10, 6)).rotation_offset(0)],
+    "45° 25° Adapter B" => &[BrickDesc::new("PB_DefaultRampInnerCorner").size((10, 15, 6)).rotation_offset(1)],
+    "45° 25° Adapter C" => &[BrickDesc::new("PB_DefaultRampCorner").size((15, 10, 6)).rotation_offset(0)],
+    "45° 25° Adapter D" => &[BrickDesc::new("PB_DefaultRampCorner").size((10, 15, 6)).rotation_offset(1)],
+    "-45°-25° Inv Adapter B" => &[BrickDesc::new("PB_DefaultRampInnerCorner").size((15, 10, 6)).rotation_offset(0).direction_override(ZNegative)],
+    "-45°-25° Inv Adapter A" => &[BrickDesc::new("PB_DefaultRampInnerCorner").size((10, 15, 6)).rotation_offset(1).direction_override(ZNegative)],
+    "-45° -25° Inv Adapter D" => &[BrickDesc::new("PB_DefaultRampCorner").size((15, 10, 6)).rotation_offset(0).direction_override(ZNegative)],
+    "-45° -25° Inv Adapter C" => &[BrickDesc::new("PB_DefaultRampCorner").size((10, 15, 6)).rotation_offset(1).direction_override(ZNegative)],
+    // 1RandomBrickPack Long Wedges
+    "16.7° 1x2 Ramp" => &[BrickDesc::new("PB_DefaultWedge").size((10, 5, 4)).rotation_offset(0)],
+    "11.31° 1x3 Ramp" => &[BrickDesc::new("PB_DefaultWedge").size((15, 5, 4)).rotation_offset(0)],
+    "8.53° 1x4 Ramp" => &[BrickDesc::new("PB_DefaultWedge").size((20, 5, 4)).rotation_offset(0)],
+    // 1RandomBrickPack Correct Octo Mappings
+    "2x2x2 Octo Elbow" => &[BrickDesc::new("B_2x_Octo_90Deg")],
+    "2x2x2 Octo - Elbow" => &[BrickDesc::new("B_2x_Octo_90Deg").direction_override(ZNegative).rotation_offset(3)],
+    "2x2x2 Octo T Vert" => &[BrickDesc::new("B_2x_Octo_T")],
+    "2x2x2 Octo Elbow Horz" => &[BrickDesc::new("B_2x_Octo_90Deg").direction_override(XPositive)],
+    "2x2x2 Octo T Horz" => &[BrickDesc::new("B_2x_Octo_T").direction_override(YNegative)],
+    "2x2x2 Octo T" => &[BrickDesc::new("B_2x_Octo_T").direction_override(YNegative).rotation_offset(2)],
+    "2x2x2 Octo T inv" => &[BrickDesc::new("B_2x_Octo_T").direction_override(YNegative).rotation_offset(0)],
+    "1x2 Octo Plate90" => &[BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((3, 0, 0))],
+    "2x2 Octo Brick90" => &[BrickDesc::new("B_2x_Octo").direction_override(YNegative)],
+};
+
+/// Literal mappings that only resemble the Blockland brick.
+pub static BRICK_MAP_APPROXIMATE: phf::Map<&'static str, &'static [BrickDesc]> = phf_map! {
+    "2x2 Disc" => &[BrickDesc::new("B_2x2F_Round")],
+    "2x2 disc Inv" => &[BrickDesc::new("B_2x2F_Round")], // 1RBP
+    "Music Brick" => &[BrickDesc::new("PB_DefaultBrick").size((5, 5, 6))],
+    // Brickadia has no corner side wedge, so this keeps the slope but not the corner cut
+    "2x2 Corner Wedge" => &[BrickDesc::new("PB_DefaultWedge").size((10, 10, 6)).rotation_offset(0)],
+    "1x4x2 Fence" => &[BrickDesc::new("PB_DefaultBrick").size((5, 4*5, 2*6)).rotation_offset(0)],
+    "2x2x1 Octo Cone" => &[BrickDesc::new("B_2x2_Round")],
+    "1x1x3 Cone" => &[
+        BrickDesc::new("B_1x1_Round").offset((0, 0, -12)),
+        BrickDesc::new("B_1x1_Round"),
+        BrickDesc::new("B_1x1_Cone").offset((0, 0, 12)),
+    ],
+    "2x2x5 Lattice" => &[
+        BrickDesc::new("PB_DefaultBrick").size((10, 10, 2)).offset((0, 0, -28)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 26)).offset((-8, -8, 0)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 26)).offset((-8, 8, 0)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 26)).offset((8, -8, 0)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 26)).offset((8, 8, 0)),
+        BrickDesc::new("PB_DefaultBrick").size((10, 10, 2)).offset((0, 0, 28)),
+    ],
+    "Gravestone" => &[BrickDesc::new("B_Gravestone")],
+    "Pumpkin" => &[BrickDesc::new("B_Pumpkin")],
+    "Pumpkin Face" => &[BrickDesc::new("B_Pumpkin_Carved")],
+    "Flower" => &[BrickDesc::new("B_Flower").offset((0, 0, -3))],
+    "Small Flower" => &[BrickDesc::new("B_Small_Flower").offset((0, 0, -3))],
+    "Bush" => &[BrickDesc::new("B_Bush").offset((0, 0, -6))],
+    "Tall Grass" => &[BrickDesc::new("B_Fern").offset((0, 0, -6))],
+    "Fern" => &[BrickDesc::new("B_Fern").offset((0, 0, -6))],
+    "House Door" => GENERIC_DOOR,
+    "Plain Door" => GENERIC_DOOR,
+    "Teledoor" => TELEDOOR,
+
+    "2x2 Octo" => &[
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, -4)),
+        BrickDesc::new("B_2x2F_Octo"),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, 4)),
+    ],
+
+    "Palm Tree" => &[
+        // Trunk
+        BrickDesc::new("PB_DefaultPole").size((5, 5, 10*6)).offset((0, 0, -12))
+            .color_override([110, 75, 40, 255]),
+        // Leaves
+        BrickDesc::new("PB_DefaultWedge").size((15, 5, 6)).offset((0, 20, 54)).rotation_offset(0)
+            .color_override([40, 120, 40, 255]),
+        BrickDesc::new("PB_DefaultWedge").size((15, 5, 6)).offset((0, -20, 54)).rotation_offset(2)
+            .color_override([40, 120, 40, 255]),
+        BrickDesc::new("PB_DefaultWedge").size((15, 5, 6)).offset((20, 0, 54)).rotation_offset(3)
+            .color_override([40, 120, 40, 255]),
+        BrickDesc::new("PB_DefaultWedge").size((15, 5, 6)).offset((-20, 0, 54)).rotation_offset(1)
+            .color_override([40, 120, 40, 255]),
+    ],
+
+    "Castle Wall" => &[
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, -10, 0)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, 10, 0)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 3*6)).offset((0, 0, -9*2)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 4*2)).offset((0, 0, 14*2)),
+    ],
+
+    "Castle Wall Arrow Slit" => &[
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, -10, 0)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, 10, 0)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 2*6)).offset((0, 0, -4*6)),
+        BrickDesc::new("PB_DefaultTile").size((5, 5, 1*6)).offset((0, 0, 5*6)),
+    ],
+
+    "1x4 Battlement" => &[
+        BrickDesc::new("PB_DefaultBrick").size((5, 4*5, 6)).offset((0, 0, -6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((0, -15, 6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((0, 5, 6)),
+    ],
+
+    "4x4 Turret" => &[
+        BrickDesc::new("PB_DefaultBrick").size((4*5, 4*5, 6)).offset((0, 0, -6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((-15, -15, 6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((-15, 15, 6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((15, -15, 6)),
+        BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)).offset((15, 15, 6)),
+    ],
+
+    "1x4x5 Window" => &[
+        BrickDesc::new("PB_DefaultBrick").size((5, 4*5, 2)).rotation_offset(0).offset((0, 0, -14*2)),
+        BrickDesc::new("PB_DefaultTile").size((5, 4*5, 5*6-2)).rotation_offset(0).offset((0, 0, 2))
+            .color_override([255, 255, 255, 76]),
+    ],
+
+    "1x4x2 Bars" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((5, 20, 2)).offset((0, 0, -10)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((5, 20, 1)).offset((0, 0, 11)),
+        BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((-15, 0, -7)),
+        BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((-5, 0, -7)),
+        BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((5, 0, -7)),
+        BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((15, 0, -7)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((15, 0, 2)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((5, 0, 2)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((-5, 0, 2)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((-15, 0, 2)),
+    ],
+
+    "Treasure Chest" => &[
+        // Body
+        BrickDesc::new("PB_DefaultMicroBrick").size((20, 10, 2)).offset((0, 0, -8)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((8, 18, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((8, -18, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-8, 18, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-8, -18, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((4, 2, 2)).offset((8, 0, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((4, 2, 2)).offset((-8, 0, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((19, 6, 2)).offset((0, 0, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((8, 10, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((8, -10, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((-8, 10, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((-8, -10, -4)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((20, 10, 4)).offset((0, 0, 2)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((20, 6, 2)).offset((0, 0, 8)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 20, 2)).offset((-8, 0, 8)).microwedge_rotate(true).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 20, 2)).offset((8, 0, 8)).microwedge_rotate(true).rotation_offset(0),
+        // Lock, only for looks
+        BrickDesc::new("PB_DefaultMicroBrick").size((4, 1, 2)).offset((-11, 0, 2)).non_priority(true)
+            .color_override([255, 255, 0, 255]).collision_override(false),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 1, 1)).offset((-11, 0, -1)).non_priority(true)
+            .color_override([255, 255, 0, 255]).collision_override(false),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 1)).offset((-11, 3, -1)).non_priority(true).microwedge_rotate(true)
+            .color_override([255, 255, 0, 255]).rotation_offset(3)
+            .direction_override(ZNegative).collision_override(false),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 1)).offset((-11, -3, -1)).non_priority(true).microwedge_rotate(true)
+            .color_override([255, 255, 0, 255]).rotation_offset(1)
+            .direction_override(ZNegative).collision_override(false),
+    ],
+
+    "32x32 Road" => &[
+        // left and right sidewalks
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, -115, 0)),
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, 115, 0)),
+        // left and right stripes
+        BRICK_ROAD_STRIPE.size((1*5, 32*5, 2)).offset((0, -65, 0)),
+        BRICK_ROAD_STRIPE.size((1*5, 32*5, 2)).offset((0, 65, 0)),
+        // lanes
+        BRICK_ROAD_LANE.size((6*5, 32*5, 2)).offset((0, -6*5, 0)),
+        BRICK_ROAD_LANE.size((6*5, 32*5, 2)).offset((0, 6*5, 0)),
+    ],
+
+    // Orientations are relative to this camera position on Beta City:
+    // 39.5712 0.0598862 14.5026 0.999998 -0.0007625 0.00180403 0.799784
+    "32x32 Road T" => &[
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, -115, 0)), // top
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-115, 115, 0)), // bottom left
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((115, 115, 0)), // bottom right
+        BRICK_ROAD_STRIPE.size((1*5, 32*5, 2)).offset((0, -65, 0)), // straight top
+        BRICK_ROAD_STRIPE.size((1*5, 32*5, 2)).offset((0, 65, 0)), // straight bottom
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, 23*5, 0)), // bottom left
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, 23*5, 0)), // bottom right
+        BRICK_ROAD_LANE.size((6*5, 32*5, 2)).offset((0, -6*5, 0)), // straight top
+        BRICK_ROAD_LANE.size((6*5, 32*5, 2)).offset((0, 6*5, 0)), // straight bottom
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, 23*5, 0)), // bottom left
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, 23*5, 0)), // bottom right
+    ],
+
+    // Orientations are relative to this camera position on Beta City:
+    // -56.5 -35 4 0 0 1 3.14159
+    "32x32 Road X" => &[
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-23*5, -23*5, 0)), // top left
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((23*5, -23*5, 0)), // top right
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-23*5, 23*5, 0)), // bottom left
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((23*5, 23*5, 0)), // bottom right
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).offset((13*5, -13*5, 0)), // corner top left
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).offset((13*5, 13*5, 0)), // corner right right
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).offset((-13*5, -13*5, 0)), // corner bottom left
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).offset((-13*5, 13*5, 0)), // corner bottom right
+        BRICK_ROAD_STRIPE.size((1*5, 12*5, 2)).rotation_offset(0).offset((-13*5, 0, 0)), // inner bottom
+        BRICK_ROAD_STRIPE.size((1*5, 12*5, 2)).rotation_offset(0).offset((13*5, 0, 0)), // inner top
+        BRICK_ROAD_STRIPE.size((1*5, 12*5, 2)).offset((0, -13*5, 0)), // inner left
+        BRICK_ROAD_STRIPE.size((1*5, 12*5, 2)).offset((0, 13*5, 0)), // inner right
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, 23*5, 0)), // right bottom
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, 23*5, 0)), // right top
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, -23*5, 0)), // left bottom
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, -23*5, 0)), // left top
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).offset((-23*5, -13*5, 0)), // bottom left
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).offset((-23*5, 13*5, 0)), // bottom right
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).offset((23*5, -13*5, 0)), // top left
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).offset((23*5, 13*5, 0)), // top right
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((-6*5, -6*5, 0)), // inner bottom left
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((-6*5, 6*5, 0)), // inner bottom right
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((6*5, -6*5, 0)), // inner top left
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((6*5, 6*5, 0)), // inner top right
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, 23*5, 0)), // right bottom
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, 23*5, 0)), // right top
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, -23*5, 0)), // left bottom
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, -23*5, 0)), // left top
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).offset((-23*5, -6*5, 0)), // bottom left
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).offset((-23*5, 6*5, 0)), // bottom right
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).offset((23*5, -6*5, 0)), // top left
+        BRICK_ROAD_LANE.size((6*5, 9*5, 2)).offset((23*5, 6*5, 0)), // top right
+    ],
+
+    // Orientations are relative to this camera position on Beta City:
+    // -25.9168 -110.523 12.5993 0.996034 0.0289472 -0.0841301 0.665224
+    "32x32 Road C" => &[
+        // sidewalks
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-115, 115, 0)), // top left
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((115, -115, 0)), // bottom right
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 23*5, 2)).rotation_offset(0).offset((115, 45, 0)), // bottom left
+        BrickDesc::new("PB_DefaultBrick").size((9*5, 23*5, 2)).offset((-45, -115, 0)), // top right
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).offset((-115, 65, 0)), // inner right
+        BRICK_ROAD_STRIPE.size((1*5, 9*5, 2)).rotation_offset(0).offset((-65, 115, 0)), // inner bottom
+        BRICK_ROAD_STRIPE.size((1*5, 22*5, 2)).offset((-50, -65, 0)), // top right
+        BRICK_ROAD_STRIPE.size((1*5, 22*5, 2)).rotation_offset(0).offset((65, 50, 0)), // bottom left
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).offset((65, -65, 0)), // bottom right
+        BRICK_ROAD_STRIPE.size((1*5, 1*5, 2)).rotation_offset(0).offset((-65, 65, 0)), // inner bottom right
+        BRICK_ROAD_LANE.size((6*5, 10*5, 2)).offset((-22*5, 6*5, 0)), // top left
+        BRICK_ROAD_LANE.size((6*5, 16*5, 2)).offset((-16*5, -6*5, 0)), // top right
+        BRICK_ROAD_LANE.size((6*5, 16*5, 2)).rotation_offset(0).offset((6*5, 16*5, 0)), // bottom left
+        BRICK_ROAD_LANE.size((6*5, 10*5, 2)).rotation_offset(0).offset((-6*5, 22*5, 0)), // left top
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((-6*5, 6*5, 0)), // inner top left
+        BRICK_ROAD_LANE.size((6*5, 6*5, 2)).offset((6*5, -6*5, 0)), // inner bottom right
+    ],
+
+    // 1RandomBrickPack
+    "2x2f Print 90" => &[BrickDesc::new("PB_DefaultSmoothTile").size((10, 10, 2)).offset((3, 0, 0)).direction_override(YPositive)],
+    "2x2f Round Ceiling" => &[BrickDesc::new("PB_DefaultPole").size((10, 10, 2))],
+    "2x2f Round Print 90" => &[BrickDesc::new("PB_DefaultPole").size((10, 10, 2)).offset((3, 0, 0)).direction_override(YNegative)],
+    "2x2x2Undercarriage" => &[
+        BrickDesc::new("PB_DefaultPole").size((10, 10, 2)).offset((0, 0, -10)),
+        BrickDesc::new("PB_DefaultPole").size((4 , 4, 4)).offset((0, 0, -4)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 5)).offset((0, 0, 5)),
+        BrickDesc::new("PB_DefaultPole").size((10, 10, 1)).offset((0, 0, 11)),
+    ],
+    "4x4f Round" => ROUND_4X4F,
+    "4x4F Round" => ROUND_4X4F,
+    "4x4 Round" => &[BrickDesc::new("B_4x4_Round")],
+    "4x4f Round Print" => PRINT_4X4F_ROUND,
+    "4x4f Round Print Ceiling" => PRINT_4X4F_ROUND,
+    "6x6f Round" => &[
+        BrickDesc::new("PB_DefaultBrick").size((20, 20, 2)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 14, 2)).offset((0, 23, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 14, 2)).offset((0, -23, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((23, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((-23, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((23, 17, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((17, 23, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((23, -17, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((17, -23, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-23, -17, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-17, -23, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-23, 17, 0)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-17, 23, 0)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-7, 28, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((7, 28, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((7, -28, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-7, -28, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((28, -7, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((28, 7, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-28, 7, 0)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-28, -7, 0)).rotation_offset(2),
+    ],
+    // Lazy half-round mappings
+    "1x1 half-round 90" => &[BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(0)],
+    "2x1 half-round 90" => &[BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(0)],
+    "4x1 half-round 90" => &[BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(0)],
+    "1x2 half-round 90" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
+    ],
+    "2x2 half-round 90" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
+    ],
+    "4x2 half-round 90" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
+    ],
+    "1x1f half-round" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((4, -3, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((1, 1, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((-3, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 2)).offset((-1, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-3, 1, 0))
+    ],
+    "1x1 half-round" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((4, -3, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((1, 1, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-3, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 6)).offset((-1, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 6)).offset((-3, 1, 0))
+    ],
+    "1x2F Half-round" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((9, -3, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((6, 1, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((2, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((-9, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((-6, 1, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((-2, 4, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 8, 2)).offset((0, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 2)).offset((0, 1, 0))
+    ],
+    "1x2 Half-round" => &[
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((9, -3, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((6, 1, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((2, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-9, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((-6, 1, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-2, 4, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 8, 6)).offset((0, -3, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 6)).offset((0, 1, 0))
+    ],
+    "6x3F Half-round" => &[
+        BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((-5, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 7, 2)).offset((-8, 23, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 7, 2)).offset((-8, -23, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((8, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((8, 17, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((2, 23, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((8, -17, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((2, -23, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-8, 28, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-8, -28, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((13, -7, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((13, 7, 0)).rotation_offset(0),
+    ],
+    "4x2F Half-round" => &[
+        BrickDesc::new("PB_DefaultBrick").size((10, 5, 2)).offset((-5, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((4, 14, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((4, -14, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroBrick").size((4, 5, 2)).offset((-5, 14, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((4, 5, 2)).offset((-5, -14, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((4, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, 19, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, -19, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((9, -5, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((9, 5, 0)).rotation_offset(0),
+    ],
+
+    // TODO: Revisit if Octo bricks become procedural
+    "1x1 Octo" => &[
+        BrickDesc::new("B_1x1F_Octo"),
+        BrickDesc::new("B_1x1F_Octo").offset((0, 0, 4)),
+        BrickDesc::new("B_1x1F_Octo").offset((0, 0, -4)),
+    ],
+    "1x1x2 Octo" => &[
+        BrickDesc::new("B_1x_Octo").offset((0, 0, -7)),
+        BrickDesc::new("B_1x_Octo").offset((0, 0, 7)),
+        BrickDesc::new("B_1x1F_Octo")
+    ],
+    "2x2x2 Octo" => &[
+        BrickDesc::new("B_2x_Octo").offset((0, 0, -2)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, 10))
+    ],
+    "2x2x2 Octo Cone" => &[
+        BrickDesc::new("B_2x_Octo_Cone").offset((0, 0, 2)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, -10))
+    ],
+    "2x2x2 Octo Cone Inv" => &[
+        BrickDesc::new("B_2x_Octo_Cone").offset((0, 0, -2)).direction_override(ZNegative),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, 10))
+    ],
+    "2x2x2 Octo Plus Vert" => &[BrickDesc::new("PB_DefaultStudded").size((10, 10, 10))],
+    "2x2x2 Octo Plus Horz" => &[BrickDesc::new("PB_DefaultStudded").size((10, 10, 10))],
+    "2x2x2 Octo Plus Plus" => &[BrickDesc::new("PB_DefaultStudded").size((10, 10, 10))],
+    "1x2 Octo Brick90" => &[
+        BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((3, 0, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 1, 10)), // TODO: replace this filler with micros to look like an octo
+        BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((-3, 0, 0)), 
+    ],
+    "2x3x2 Octo Offset" => &[
+        BrickDesc::new("B_2x2F_Octo").offset((0, -5, -10)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, -3, -6)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, -1, -2)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 1, 2)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 3, 6)),
+        BrickDesc::new("B_2x2F_Octo").offset((0, 5, 10)),
+    ],
+    "2x2x1 Octo Cone Inv" => &[
+        BrickDesc::new("B_2x2F_Octo").offset((0, 0, 4)),
+        BrickDesc::new("B_1x1F_Octo").offset((0, 0, -4)),
+        BrickDesc::new("PB_DefaultPole").size((7, 7, 2))
+    ],
+
+    "45° Crest Plus" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, 5, 1)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, -5, 1)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, 5, 1)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, -5, 1)).rotation_offset(2),
+    ],
+    "25° Crest Plus" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, 5, -1)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, -5, -1)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, 5, -1)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, -5, -1)).rotation_offset(2),
+    ],
+    "45° Crest T" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, 5, 1)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, 5, 1)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 5)).microwedge_rotate(true).offset((0, -5, 1)).rotation_offset(3),
+    ],
+    "25° Crest T" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, 5, -1)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, 5, -1)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 3)).microwedge_rotate(true).offset((0, -5, -1)).rotation_offset(3),
+    ],
+
+    "Antenna" => &[
+        BrickDesc::new("B_1x1F_Round").offset((0, 0, -28)),
+        BrickDesc::new("PB_DefaultPole").size((4, 4, 2)).offset((0, 0, -24)),
+        BrickDesc::new("PB_DefaultPole").size((2, 2, 24)).offset((0, 0, 2))
+    ],
+    "1x2Log" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 10, 6)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((5, 4, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((5, -4, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((-5, 4, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((-5, -4, 0)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((8, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-2, 4, 0)).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-8, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((2, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-8, 4, 0)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((2, 4, 0)).rotation_offset(1),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((8, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-2, -4, 0)).rotation_offset(3),
+    ],
+    "1x2 Ridged" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((3, 10, 6)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((9, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((7, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((5, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((3, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((1, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-1, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-3, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-5, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-7, -4, 0)).rotation_offset(2),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-9, -4, 0)).rotation_offset(3),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -5)).rotation_offset(1).microwedge_rotate(true),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -3)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -1)).rotation_offset(1).microwedge_rotate(true),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 1)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 3)).rotation_offset(1).microwedge_rotate(true),
+        BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 5)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
+    ],
+    "2x4x3 Tube" => &[
+        BrickDesc::new("PB_DefaultTile").size((20, 10, 2)).offset((0, 0, -16)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 10, 14)).offset((0, -18, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 10, 14)).offset((0, 18, 0)),
+        BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((0, 0, 16)),
+    ],
+    "2x4x3 Windscreen" => &[
+        BrickDesc::new("PB_DefaultTile").size((20, 10, 2)).offset((0, 0, -16)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, -18, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, 18, 0)),
+        BrickDesc::new("PB_DefaultBrick").size((20, 5, 2)).offset((-5, 0, 16)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, 18, 2)).microwedge_rotate(true).rotation_offset(0),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, -18, 2)).microwedge_rotate(true).rotation_offset(0),
+    ],
+    "2x4x3 Windscreen Inv" => &[
+        BrickDesc::new("PB_DefaultTile").size((20, 5, 2)).offset((-5, 0, -16)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, -18, 0)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, 18, 0)),
+        BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((0, 0, 16)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, 18, -2)).microwedge_rotate(true).rotation_offset(2).direction_override(ZNegative),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, -18, -2)).microwedge_rotate(true).rotation_offset(2).direction_override(ZNegative),
+    ],
+    "1x4x2vertwing" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((15, 5, 2)).offset((0, 5, -10)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((5, 1, 9)).offset((0, -5, 1)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 9)).offset((0, -15, 1)).microwedge_rotate(true).direction_override(ZNegative),
+        BrickDesc::new("PB_DefaultMicroWedge").size((10, 1, 9)).offset((0, 10, 1)).microwedge_rotate(true),
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 5, 1)).offset((0, -10, 11)),
+    ],
+    "1x5x3vertwing" => &[
+        BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 2)).offset((0, 5, -16)),
+        BrickDesc::new("PB_DefaultMicroBrick").size((5, 1, 15)).offset((0, -10, 1)),
+        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 15)).offset((0, -20, 1)).microwedge_rotate(true).direction_override(ZNegative),
+        BrickDesc::new("PB_DefaultMicroWedge").size((15, 1, 15)).offset((0, 10, 1)).microwedge_rotate(true),
+        BrickDesc::new("PB_DefaultMicroBrick").size((10, 5, 1)).offset((0, -15, 17)),
+    ],
+};
+
+/// Water bricks from the Undulo and water cube add-ons, see `is_water`.
+const WATER_PATTERN: &str = r"^(?:(?P<width>\d+)x(?P<length>\d+)(?:x(?P<height>\d+))? )?(?:Undulo(?: Water)?|Water)(?: (?P<cube>Cube)| (?P<plate>Plate)| Brick)?$";
+
+/// Translucent blue, so the glass material is used with the default options.
+const WATER_COLOR: [u8; 4] = [40, 100, 200, 128];
+
+/// Checkpoints, capture points and spawns from minigame add-ons, see `minigame_kind`.
+const MINIGAME_PATTERN: &str = r"^(?:(?P<width>\d+)x(?P<length>\d+)F? )?(?:(?P<checkpoint>Checkpoint)|(?P<capture>(?:Capture|Control) Point)|(?P<spawn>(?:Team )?Spawn Point))$";
+
+/// Teleporter pads from add-ons, flat unless the name says otherwise, see `is_portal`.
+const TELEPORTER_PATTERN: &str = r"^(?:(?P<width>\d+)x(?P<length>\d+)(?P<plate>F)? )?Teleporter(?: Pad)?$";
+
+/// Whether a print is one of the road textures, which mappings of printed bricks can lay
+/// out with road tiles instead of dropping.
+fn is_road_print(print: &str) -> bool {
+    let name = print.rsplit('/').next().unwrap_or(print);
+    name.to_ascii_lowercase().contains("road")
+}
+
+/// A 45° ramp with a road print, in the lane color. Prints with a stripe or line down
+/// the middle get a thin stripe-colored ramp there, splitting the lane in two.
+fn road_ramp((x, y, z): (u32, u32, u32), print: &str) -> BrickMapping {
+    let lane = BrickDesc { asset: "PB_DefaultRamp", ..BRICK_ROAD_LANE }.rotation_offset(0).approximate(true);
+    let print = print.to_ascii_lowercase();
+    if !print.contains("stripe") && !print.contains("line") {
+        return vec![lane.size((x, y, z))];
+    }
+
+    // Keep the halves whole units
+    let stripe = if y % 2 == 0 { 2 } else { 1 };
+    let half = (y - stripe) / 2;
+    let side = (stripe + half) as i32;
+    vec![
+        lane.clone().size((x, half, z)).offset((0, -side, 0)),
+        BrickDesc { asset: "PB_DefaultRamp", ..BRICK_ROAD_STRIPE }.size((x, stripe, z)).rotation_offset(0).approximate(true),
+        lane.size((x, half, z)).offset((0, side, 0)),
+    ]
+}
+
+/// Whether the brick is water, for `ConvertOptions::skip_water`.
+pub fn is_water(ui_name: &str) -> bool {
+    WATER.is_match(ui_name)
+}
+
+/// Whether the brick is a teledoor or teleporter, written as a portal placeholder and
+/// listed in `ConvertReport::portals`.
+pub fn is_portal(ui_name: &str) -> bool {
+    ui_name == "Teledoor" || TELEPORTER.is_match(ui_name)
+}
+
+/// The kind of minigame brick, written as a marker and listed in
+/// `ConvertReport::minigame_bricks`.
+pub fn minigame_kind(ui_name: &str) -> Option<MinigameKind> {
+    let captures = MINIGAME.captures(ui_name)?;
+    Some(if captures.name("checkpoint").is_some() {
+        MinigameKind::Checkpoint
+    } else if captures.name("capture").is_some() {
+        MinigameKind::CapturePoint
+    } else {
+        MinigameKind::Spawn
+    })
+}
+
 lazy_static! {
+    static ref MINIGAME: Regex = Regex::new(MINIGAME_PATTERN).expect("failed to compile regex");
+    static ref WATER: Regex = Regex::new(WATER_PATTERN).expect("failed to compile regex");
+    static ref TELEPORTER: Regex = Regex::new(TELEPORTER_PATTERN).expect("failed to compile regex");
+
     static ref TILE_PRINTS: HashSet<&'static str> = vec![
         "1x2f/blank",
         "2x2f/blank",
     ].into_iter().collect();
 
-    static ref BRICK_ROAD_LANE: BrickDesc = BrickDesc::new("PB_DefaultTile")
-        .color_override(brs::Color::from_rgba(51, 51, 51, 255));
-    static ref BRICK_ROAD_STRIPE: BrickDesc = BrickDesc::new("PB_DefaultTile")
-        .color_override(brs::Color::from_rgba(254, 254, 232, 255));
-    static ref GENERIC_DOOR: BrickMapping = vec![
-        //frame
-        BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, -35)),
-        BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 1)).offset((0, 0, 35)),
-        BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, 19, 0)),
-        BrickDesc::new("PB_DefaultMicroBrick").size((1, 5, 34)).offset((0, -19, 0)),
-        //door
-        BrickDesc::new("PB_DefaultMicroBrick").size((18, 1, 34)),
-        //handle
-        BrickDesc::new("PB_DefaultMicroBrick").size((3, 1, 3)).offset((2, 12, 0)),
-        BrickDesc::new("PB_DefaultMicroBrick").size((3, 1, 3)).offset((-2, 12, 0)),
-    ];
-    static ref PRINT_4X4F_ROUND: BrickMapping = vec![
-        BrickDesc::new("PB_DefaultMicroBrick").size((18, 10, 2)),
-        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, 14, 0)).rotation_offset(0),
-        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, -14, 0)).rotation_offset(3),
-        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, -14, 0)).rotation_offset(2),
-        BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, 14, 0)).rotation_offset(1),
-        BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((14, 0, 0)),
-        BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((-14, 0, 0)),
-        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, 19, 0)),
-        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((5, 19, 0)).rotation_offset(0),
-        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((5, -19, 0)).rotation_offset(3),
-        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, -19, 0)).rotation_offset(2),
-        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((19, -5, 0)).rotation_offset(3),
-        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((19, 5, 0)).rotation_offset(0),
-        BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-19, 5, 0)).rotation_offset(1),
-        BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-19, -5, 0)).rotation_offset(2),
-    ];
-
-    pub static ref BRICK_MAP_LITERAL: HashMap<&'static str, BrickMapping> = brick_map_literal![
-        // # Correct mappings
-
-        "1x1 Cone" => BrickDesc::new("B_1x1_Cone"),
-        "2x2x2 Cone" => BrickDesc::new("B_2x2_Cone"),
-        "1x1 cone Inv" => BrickDesc::new("B_1x1_Cone").direction_override(ZNegative), // 1RandomBrickPack
-        "2x2x2 cone Inv" => BrickDesc::new("B_2x2_Cone").direction_override(ZNegative), // 1RBP
-        "1x1 Round" => BrickDesc::new("B_1x1_Round"),
-        "1x1 Octo Plate" => BrickDesc::new("B_1x1F_Octo"),
-        "1x1F Round" => BrickDesc::new("B_1x1F_Round"),
-        "2x2 Round" => BrickDesc::new("B_2x2_Round"),
-        "2x2F Round" => BrickDesc::new("B_2x2F_Round"),
-        "Pine Tree" => BrickDesc::new("B_Pine_Tree").offset((0, 0, -6)),
-        "2x2 Corner" => BrickDesc::new("B_2x2_Corner").rotation_offset(0),
-        "2x2 Octo Plate" => BrickDesc::new("B_2x2F_Octo"),
-        "8x8 Grill" => BrickDesc::new("B_8x8_Lattice_Plate"),
-        "1x4x2 Picket" => BrickDesc::new("B_Picket_Fence"),
-
-        // 1RandomBrickPack 45° to 25° Ramp Adapters
-        "45° 25° Adapter A" => BrickDesc::new("PB_DefaultRampInnerCorner").size((15, 10, 6)).rotation_offset(0),
-        "45° 25° Adapter B" => BrickDesc::new("PB_DefaultRampInnerCorner").size((10, 15, 6)).rotation_offset(1),
-        "45° 25° Adapter C" => BrickDesc::new("PB_DefaultRampCorner").size((15, 10, 6)).rotation_offset(0),
-        "45° 25° Adapter D" => BrickDesc::new("PB_DefaultRampCorner").size((10, 15, 6)).rotation_offset(1),
-        "-45°-25° Inv Adapter B" => BrickDesc::new("PB_DefaultRampInnerCorner").size((15, 10, 6)).rotation_offset(0).direction_override(ZNegative),
-        "-45°-25° Inv Adapter A" => BrickDesc::new("PB_DefaultRampInnerCorner").size((10, 15, 6)).rotation_offset(1).direction_override(ZNegative),
-        "-45° -25° Inv Adapter D" => BrickDesc::new("PB_DefaultRampCorner").size((15, 10, 6)).rotation_offset(0).direction_override(ZNegative),
-        "-45° -25° Inv Adapter C" => BrickDesc::new("PB_DefaultRampCorner").size((10, 15, 6)).rotation_offset(1).direction_override(ZNegative),
-        // 1RandomBrickPack Long Wedges
-        "16.7° 1x2 Ramp" => BrickDesc::new("PB_DefaultWedge").size((10, 5, 4)).rotation_offset(0),
-        "11.31° 1x3 Ramp" => BrickDesc::new("PB_DefaultWedge").size((15, 5, 4)).rotation_offset(0),
-        "8.53° 1x4 Ramp" => BrickDesc::new("PB_DefaultWedge").size((20, 5, 4)).rotation_offset(0),
-        // 1RandomBrickPack Correct Octo Mappings
-        "2x2x2 Octo Elbow" => BrickDesc::new("B_2x_Octo_90Deg"),
-        "2x2x2 Octo - Elbow" => BrickDesc::new("B_2x_Octo_90Deg").direction_override(ZNegative).rotation_offset(3),
-        "2x2x2 Octo T Vert" => BrickDesc::new("B_2x_Octo_T"),
-        "2x2x2 Octo Elbow Horz" => BrickDesc::new("B_2x_Octo_90Deg").direction_override(XPositive),
-        "2x2x2 Octo T Horz" => BrickDesc::new("B_2x_Octo_T").direction_override(YNegative),
-        "2x2x2 Octo T" => BrickDesc::new("B_2x_Octo_T").direction_override(YNegative).rotation_offset(2),
-        "2x2x2 Octo T inv" => BrickDesc::new("B_2x_Octo_T").direction_override(YNegative).rotation_offset(0),
-        "1x2 Octo Plate90" => BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((3, 0, 0)),
-        "2x2 Octo Brick90" => BrickDesc::new("B_2x_Octo").direction_override(YNegative),
-
-        // # Approximate mappings
-
-        "2x2 Disc" => BrickDesc::new("B_2x2F_Round"),
-        "2x2 disc Inv" => BrickDesc::new("B_2x2F_Round"), // 1RBP
-        "Music Brick" => BrickDesc::new("PB_DefaultBrick").size((5, 5, 6)),
-        "1x4x2 Fence" => BrickDesc::new("PB_DefaultBrick").size((5, 4*5, 2*6)).rotation_offset(0),
-        "2x2x1 Octo Cone" => BrickDesc::new("B_2x2_Round"),
-        "Gravestone" => BrickDesc::new("B_Gravestone"),
-        "House Door" => GENERIC_DOOR.clone(),
-        "Plain Door" => GENERIC_DOOR.clone(),
-
-        "2x2 Octo" => vec![
-            BrickDesc::new("B_2x2F_Octo").offset((0, 0, -4)),
-            BrickDesc::new("B_2x2F_Octo"),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 0, 4)),
-        ],
-
-        "Castle Wall" => vec![
-            BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, -10, 0)),
-            BrickDesc::new("PB_DefaultTile").size((5, 5, 6*6)).offset((0, 10, 0)),
-            BrickDesc::new("PB_DefaultTile").size((5, 5, 3*6)).offset((0, 0, -9*2)),
-            BrickDesc::new("PB_DefaultTile").size((5, 5, 4*2)).offset((0, 0, 14*2)),
-        ],
-
-        "1x4x5 Window" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((5, 4*5, 2)).rotation_offset(0).offset((0, 0, -14*2)),
-            BrickDesc::new("PB_DefaultTile").size((5, 4*5, 5*6-2)).rotation_offset(0).offset((0, 0, 2))
-                .color_override(brs::Color::from_rgba(255, 255, 255, 76)),
-        ],
-
-        "1x4x2 Bars" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((5, 20, 2)).offset((0, 0, -10)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((5, 20, 1)).offset((0, 0, 11)),
-            BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((-15, 0, -7)),
-            BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((-5, 0, -7)),
-            BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((5, 0, -7)),
-            BrickDesc::new("PB_DefaultPole").size((3, 3, 1)).offset((15, 0, -7)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((15, 0, 2)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((5, 0, 2)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((-5, 0, 2)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 8)).offset((-15, 0, 2)),
-        ],
-
-        "Treasure Chest" => vec![
-            // Body
-            BrickDesc::new("PB_DefaultMicroBrick").size((20, 10, 2)).offset((0, 0, -8)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((8, 18, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((8, -18, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-8, 18, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-8, -18, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 2, 2)).offset((8, 0, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 2, 2)).offset((-8, 0, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((19, 6, 2)).offset((0, 0, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((8, 10, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((8, -10, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((-8, 10, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((6, 1, 2)).offset((-8, -10, -4)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((20, 10, 4)).offset((0, 0, 2)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((20, 6, 2)).offset((0, 0, 8)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 20, 2)).offset((-8, 0, 8)).microwedge_rotate(true).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 20, 2)).offset((8, 0, 8)).microwedge_rotate(true).rotation_offset(0),
-            // Lock
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 1, 2)).offset((-11, 0, 2)).non_priority(true)
-                .color_override(brs::Color::from_rgba(255, 255, 0, 255)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 1, 1)).offset((-11, 0, -1)).non_priority(true)
-                .color_override(brs::Color::from_rgba(255, 255, 0, 255)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 1)).offset((-11, 3, -1)).non_priority(true).microwedge_rotate(true)
-                .color_override(brs::Color::from_rgba(255, 255, 0, 255)).rotation_offset(3)
-                .direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 1)).offset((-11, -3, -1)).non_priority(true).microwedge_rotate(true)
-                .color_override(brs::Color::from_rgba(255, 255, 0, 255)).rotation_offset(1)
-                .direction_override(ZNegative),
-        ],
-
-        "32x32 Road" => vec![
-            // left and right sidewalks
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, -115, 0)),
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, 115, 0)),
-            // left and right stripes
-            BRICK_ROAD_STRIPE.clone().size((1*5, 32*5, 2)).offset((0, -65, 0)),
-            BRICK_ROAD_STRIPE.clone().size((1*5, 32*5, 2)).offset((0, 65, 0)),
-            // lanes
-            BRICK_ROAD_LANE.clone().size((6*5, 32*5, 2)).offset((0, -6*5, 0)),
-            BRICK_ROAD_LANE.clone().size((6*5, 32*5, 2)).offset((0, 6*5, 0)),
-        ],
-
-        // Orientations are relative to this camera position on Beta City:
-        // 39.5712 0.0598862 14.5026 0.999998 -0.0007625 0.00180403 0.799784
-        "32x32 Road T" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 32*5, 2)).offset((0, -115, 0)), // top
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-115, 115, 0)), // bottom left
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((115, 115, 0)), // bottom right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 32*5, 2)).offset((0, -65, 0)), // straight top
-            BRICK_ROAD_STRIPE.clone().size((1*5, 32*5, 2)).offset((0, 65, 0)), // straight bottom
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, 23*5, 0)), // bottom left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, 23*5, 0)), // bottom right
-            BRICK_ROAD_LANE.clone().size((6*5, 32*5, 2)).offset((0, -6*5, 0)), // straight top
-            BRICK_ROAD_LANE.clone().size((6*5, 32*5, 2)).offset((0, 6*5, 0)), // straight bottom
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, 23*5, 0)), // bottom left
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, 23*5, 0)), // bottom right
-        ],
-
-        // Orientations are relative to this camera position on Beta City:
-        // -56.5 -35 4 0 0 1 3.14159
-        "32x32 Road X" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-23*5, -23*5, 0)), // top left
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((23*5, -23*5, 0)), // top right
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-23*5, 23*5, 0)), // bottom left
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((23*5, 23*5, 0)), // bottom right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).offset((13*5, -13*5, 0)), // corner top left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).offset((13*5, 13*5, 0)), // corner right right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).offset((-13*5, -13*5, 0)), // corner bottom left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).offset((-13*5, 13*5, 0)), // corner bottom right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 12*5, 2)).rotation_offset(0).offset((-13*5, 0, 0)), // inner bottom
-            BRICK_ROAD_STRIPE.clone().size((1*5, 12*5, 2)).rotation_offset(0).offset((13*5, 0, 0)), // inner top
-            BRICK_ROAD_STRIPE.clone().size((1*5, 12*5, 2)).offset((0, -13*5, 0)), // inner left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 12*5, 2)).offset((0, 13*5, 0)), // inner right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, 23*5, 0)), // right bottom
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, 23*5, 0)), // right top
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((-13*5, -23*5, 0)), // left bottom
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((13*5, -23*5, 0)), // left top
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).offset((-23*5, -13*5, 0)), // bottom left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).offset((-23*5, 13*5, 0)), // bottom right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).offset((23*5, -13*5, 0)), // top left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).offset((23*5, 13*5, 0)), // top right
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((-6*5, -6*5, 0)), // inner bottom left
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((-6*5, 6*5, 0)), // inner bottom right
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((6*5, -6*5, 0)), // inner top left
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((6*5, 6*5, 0)), // inner top right
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, 23*5, 0)), // right bottom
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, 23*5, 0)), // right top
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((-6*5, -23*5, 0)), // left bottom
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).rotation_offset(0).offset((6*5, -23*5, 0)), // left top
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).offset((-23*5, -6*5, 0)), // bottom left
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).offset((-23*5, 6*5, 0)), // bottom right
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).offset((23*5, -6*5, 0)), // top left
-            BRICK_ROAD_LANE.clone().size((6*5, 9*5, 2)).offset((23*5, 6*5, 0)), // top right
-        ],
-
-        // Orientations are relative to this camera position on Beta City:
-        // -25.9168 -110.523 12.5993 0.996034 0.0289472 -0.0841301 0.665224
-        "32x32 Road C" => vec![
-            // sidewalks
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((-115, 115, 0)), // top left
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 9*5, 2)).offset((115, -115, 0)), // bottom right
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 23*5, 2)).rotation_offset(0).offset((115, 45, 0)), // bottom left
-            BrickDesc::new("PB_DefaultBrick").size((9*5, 23*5, 2)).offset((-45, -115, 0)), // top right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).offset((-115, 65, 0)), // inner right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 9*5, 2)).rotation_offset(0).offset((-65, 115, 0)), // inner bottom
-            BRICK_ROAD_STRIPE.clone().size((1*5, 22*5, 2)).offset((-50, -65, 0)), // top right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 22*5, 2)).rotation_offset(0).offset((65, 50, 0)), // bottom left
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).offset((65, -65, 0)), // bottom right
-            BRICK_ROAD_STRIPE.clone().size((1*5, 1*5, 2)).rotation_offset(0).offset((-65, 65, 0)), // inner bottom right
-            BRICK_ROAD_LANE.clone().size((6*5, 10*5, 2)).offset((-22*5, 6*5, 0)), // top left
-            BRICK_ROAD_LANE.clone().size((6*5, 16*5, 2)).offset((-16*5, -6*5, 0)), // top right
-            BRICK_ROAD_LANE.clone().size((6*5, 16*5, 2)).rotation_offset(0).offset((6*5, 16*5, 0)), // bottom left
-            BRICK_ROAD_LANE.clone().size((6*5, 10*5, 2)).rotation_offset(0).offset((-6*5, 22*5, 0)), // left top
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((-6*5, 6*5, 0)), // inner top left
-            BRICK_ROAD_LANE.clone().size((6*5, 6*5, 2)).offset((6*5, -6*5, 0)), // inner bottom right
-        ],
-
-        // 1RandomBrickPack
-        "2x2f Print 90" => BrickDesc::new("PB_DefaultSmoothTile").size((10, 10, 2)).offset((3, 0, 0)).direction_override(YPositive),
-        "2x2f Round Ceiling" => BrickDesc::new("PB_DefaultPole").size((10, 10, 2)),
-        "2x2f Round Print 90" => BrickDesc::new("PB_DefaultPole").size((10, 10, 2)).offset((3, 0, 0)).direction_override(YNegative),
-        "2x2x2Undercarriage" => vec![
-            BrickDesc::new("PB_DefaultPole").size((10, 10, 2)).offset((0, 0, -10)),
-            BrickDesc::new("PB_DefaultPole").size((4 , 4, 4)).offset((0, 0, -4)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 5)).offset((0, 0, 5)),
-            BrickDesc::new("PB_DefaultPole").size((10, 10, 1)).offset((0, 0, 11)),
-        ],
-        "4x4f Round" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((10, 10, 2)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, 14, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((14, -14, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, -14, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((-14, 14, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 10, 2)).offset((0, 14, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 10, 2)).offset((0, -14, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((14, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((-14, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, 19, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((5, 19, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((5, -19, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, -19, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((19, -5, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((19, 5, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-19, 5, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-19, -5, 0)).rotation_offset(2),
-        ],
-        "4x4f Round Print" => PRINT_4X4F_ROUND.clone(),
-        "4x4f Round Print Ceiling" => PRINT_4X4F_ROUND.clone(),
-        "6x6f Round" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((20, 20, 2)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 14, 2)).offset((0, 23, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 14, 2)).offset((0, -23, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((23, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((-23, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((23, 17, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((17, 23, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((23, -17, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((17, -23, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-23, -17, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-17, -23, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-23, 17, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((-17, 23, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-7, 28, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((7, 28, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((7, -28, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-7, -28, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((28, -7, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((28, 7, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-28, 7, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-28, -7, 0)).rotation_offset(2),
-        ],
-        // Lazy half-round mappings
-        "1x1 half-round 90" => BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(0),
-        "2x1 half-round 90" => BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(0),
-        "4x1 half-round 90" => BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(0),
-        "1x2 half-round 90" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 5, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
-        ],
-        "2x2 half-round 90" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
-        ],
-        "4x2 half-round 90" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(0).offset((5, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 20, 6)).microwedge_rotate(true).rotation_offset(2).offset((-5, 0, 0))
-        ],
-        "1x1f half-round" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((4, -3, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((1, 1, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((-3, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 2)).offset((-1, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 2)).offset((-3, 1, 0))
-        ],
-        "1x1 half-round" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((4, -3, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((1, 1, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-3, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 6)).offset((-1, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 2, 6)).offset((-3, 1, 0))
-        ],
-        "1x2F Half-round" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((9, -3, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((6, 1, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((2, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 2)).offset((-9, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 2)).offset((-6, 1, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 2)).offset((-2, 4, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 8, 2)).offset((0, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 2)).offset((0, 1, 0))
-        ],
-        "1x2 Half-round" => vec![
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((9, -3, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((6, 1, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((2, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-9, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 2, 6)).offset((-6, 1, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-2, 4, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 8, 6)).offset((0, -3, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 4, 6)).offset((0, 1, 0))
-        ],
-        "6x3F Half-round" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((-5, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 7, 2)).offset((-8, 23, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 7, 2)).offset((-8, -23, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((14, 3, 2)).offset((8, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((8, 17, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((2, 23, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((8, -17, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((3, 3, 2)).offset((2, -23, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((-8, 28, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((-8, -28, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((7, 2, 2)).offset((13, -7, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 7, 2)).offset((13, 7, 0)).rotation_offset(0),
-        ],
-        "4x2F Half-round" => vec![
-            BrickDesc::new("PB_DefaultBrick").size((10, 5, 2)).offset((-5, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((4, 14, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((4, 4, 2)).offset((4, -14, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 5, 2)).offset((-5, 14, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((4, 5, 2)).offset((-5, -14, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 4, 2)).offset((4, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((-5, 19, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((-5, -19, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 2)).offset((9, -5, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 5, 2)).offset((9, 5, 0)).rotation_offset(0),
-        ],
-
-        // TODO: Revisit if Octo bricks become procedural
-        "1x1 Octo" => vec![
-            BrickDesc::new("B_1x1F_Octo"),
-            BrickDesc::new("B_1x1F_Octo").offset((0, 0, 4)),
-            BrickDesc::new("B_1x1F_Octo").offset((0, 0, -4)),
-        ],
-        "1x1x2 Octo" => vec![
-            BrickDesc::new("B_1x_Octo").offset((0, 0, -7)),
-            BrickDesc::new("B_1x_Octo").offset((0, 0, 7)),
-            BrickDesc::new("B_1x1F_Octo")
-        ],
-        "2x2x2 Octo" => vec![
-            BrickDesc::new("B_2x_Octo").offset((0, 0, -2)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 0, 10))
-        ],
-        "2x2x2 Octo Cone Inv" => vec![
-            BrickDesc::new("B_2x_Octo_Cone").offset((0, 0, -2)).direction_override(ZNegative),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 0, 10))
-        ],
-        "2x2x2 Octo Plus Vert" => BrickDesc::new("PB_DefaultStudded").size((10, 10, 10)),
-        "2x2x2 Octo Plus Horz" => BrickDesc::new("PB_DefaultStudded").size((10, 10, 10)),
-        "2x2x2 Octo Plus Plus" => BrickDesc::new("PB_DefaultStudded").size((10, 10, 10)),
-        "1x2 Octo Brick90" => vec![
-            BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((3, 0, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 1, 10)), // TODO: replace this filler with micros to look like an octo
-            BrickDesc::new("B_2x2F_Octo").direction_override(YNegative).offset((-3, 0, 0)), 
-        ],
-        "2x3x2 Octo Offset" => vec![
-            BrickDesc::new("B_2x2F_Octo").offset((0, -5, -10)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, -3, -6)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, -1, -2)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 1, 2)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 3, 6)),
-            BrickDesc::new("B_2x2F_Octo").offset((0, 5, 10)),
-        ],
-        "2x2x1 Octo Cone Inv" => vec![
-            BrickDesc::new("B_2x2F_Octo").offset((0, 0, 4)),
-            BrickDesc::new("B_1x1F_Octo").offset((0, 0, -4)),
-            BrickDesc::new("PB_DefaultPole").size((7, 7, 2))
-        ],
-
-        "45° Crest Plus" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, 5, 1)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, -5, 1)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, 5, 1)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, -5, 1)).rotation_offset(2),
-        ],
-        "25° Crest Plus" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, 5, -1)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, -5, -1)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, 5, -1)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, -5, -1)).rotation_offset(2),
-        ],
-        "45° Crest T" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((5, 5, 1)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 5)).offset((-5, 5, 1)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 5)).microwedge_rotate(true).offset((0, -5, 1)).rotation_offset(3),
-        ],
-        "25° Crest T" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 10, 1)).offset((0, 0, -5)),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((5, 5, -1)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedgeInnerCorner").size((5, 5, 3)).offset((-5, 5, -1)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 10, 3)).microwedge_rotate(true).offset((0, -5, -1)).rotation_offset(3),
-        ],
-
-        "Antenna" => vec![
-            BrickDesc::new("B_1x1F_Round").offset((0, 0, -28)),
-            BrickDesc::new("PB_DefaultPole").size((4, 4, 2)).offset((0, 0, -24)),
-            BrickDesc::new("PB_DefaultPole").size((2, 2, 24)).offset((0, 0, 2))
-        ],
-        "1x2Log" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 10, 6)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((5, 4, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((5, -4, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((-5, 4, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, 6)).offset((-5, -4, 0)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((8, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-2, 4, 0)).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((-8, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((2, 1, 6)).offset((2, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-8, 4, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((2, 4, 0)).rotation_offset(1),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((8, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 2, 6)).offset((-2, -4, 0)).rotation_offset(3),
-        ],
-        "1x2 Ridged" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((3, 10, 6)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((9, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((7, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((5, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((3, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((1, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-1, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-3, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-5, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-7, -4, 0)).rotation_offset(2),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 1, 6)).offset((-9, -4, 0)).rotation_offset(3),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -5)).rotation_offset(1).microwedge_rotate(true),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -3)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, -1)).rotation_offset(1).microwedge_rotate(true),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 1)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 3)).rotation_offset(1).microwedge_rotate(true),
-            BrickDesc::new("PB_DefaultMicroWedge").size((1, 10, 1)).offset((0, 4, 5)).rotation_offset(3).microwedge_rotate(true).direction_override(ZNegative),
-        ],
-        "2x4x3 Tube" => vec![
-            BrickDesc::new("PB_DefaultTile").size((20, 10, 2)).offset((0, 0, -16)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 10, 14)).offset((0, -18, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 10, 14)).offset((0, 18, 0)),
-            BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((0, 0, 16)),
-        ],
-        "2x4x3 Windscreen" => vec![
-            BrickDesc::new("PB_DefaultTile").size((20, 10, 2)).offset((0, 0, -16)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, -18, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, 18, 0)),
-            BrickDesc::new("PB_DefaultBrick").size((20, 5, 2)).offset((-5, 0, 16)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, 18, 2)).microwedge_rotate(true).rotation_offset(0),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, -18, 2)).microwedge_rotate(true).rotation_offset(0),
-        ],
-        "2x4x3 Windscreen Inv" => vec![
-            BrickDesc::new("PB_DefaultTile").size((20, 5, 2)).offset((-5, 0, -16)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, -18, 0)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((2, 5, 14)).offset((-5, 18, 0)),
-            BrickDesc::new("PB_DefaultBrick").size((20, 10, 2)).offset((0, 0, 16)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, 18, -2)).microwedge_rotate(true).rotation_offset(2).direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 2, 16)).offset((5, -18, -2)).microwedge_rotate(true).rotation_offset(2).direction_override(ZNegative),
-        ],
-        "1x4x2vertwing" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((15, 5, 2)).offset((0, 5, -10)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((5, 1, 9)).offset((0, -5, 1)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 9)).offset((0, -15, 1)).microwedge_rotate(true).direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((10, 1, 9)).offset((0, 10, 1)).microwedge_rotate(true),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 5, 1)).offset((0, -10, 11)),
-        ],
-        "1x5x3vertwing" => vec![
-            BrickDesc::new("PB_DefaultMicroBrick").size((20, 5, 2)).offset((0, 5, -16)),
-            BrickDesc::new("PB_DefaultMicroBrick").size((5, 1, 15)).offset((0, -10, 1)),
-            BrickDesc::new("PB_DefaultMicroWedge").size((5, 1, 15)).offset((0, -20, 1)).microwedge_rotate(true).direction_override(ZNegative),
-            BrickDesc::new("PB_DefaultMicroWedge").size((15, 1, 15)).offset((0, 10, 1)).microwedge_rotate(true),
-            BrickDesc::new("PB_DefaultMicroBrick").size((10, 5, 1)).offset((0, -15, 17)),
-        ],
+    /// Interactive bricks that can't be converted, with the size of their footprint for
+    /// `ConvertOptions::placeholders`.
+    pub static ref PLACEHOLDER_SIZES: HashMap<&'static str, (u32, u32, u32)> = map![
+        "Vehicle Spawn" => (8*5, 8*5, 2),
+        "Horse Spawn" => (4*5, 4*5, 2),
+        "Boat Spawn" => (8*5, 8*5, 2),
+        // Jeep's Very Simple (JVS) content
+        "JVS Door" => (5, 4*5, 5*6),
+        "JVS Door Frame" => (5, 4*5, 5*6),
+        "JVS Switch" => (5, 5, 6),
+        "JVS Button" => (5, 5, 2),
     ];
 
     pub static ref BRICK_MAP_REGEX: Vec<(Regex, RegexHandler)> = brick_map_regex![
@@ -546,7 +734,8 @@ lazy_static! {
             };
 
             let print = captures.get(6).is_some();
-            let asset = if z == 2 && print && TILE_PRINTS.contains(from.base.print.as_str()) {
+            let blank = TILE_PRINTS.contains(from.base.print.as_str());
+            let asset = if z == 2 && print && blank {
                 "PB_DefaultTile"
             } else if z == 2 && print {
                 "PB_DefaultSmoothTile"
@@ -556,23 +745,43 @@ lazy_static! {
             let rotation_offset = if print { 0 } else { 1 };
             let dir = if captures.get(7).is_some() { ZNegative } else { ZPositive };
 
-            Some(vec![BrickDesc::new(asset)
+            let desc = BrickDesc::new(asset)
                 .size((width * 5, length * 5, z))
                 .rotation_offset(rotation_offset)
-                .direction_override(dir)])
+                .direction_override(dir);
+
+            // Plates are printed on top, taller bricks on the front
+            Some(match (print, z) {
+                (false, _) => vec![desc],
+                // Unless `ConvertOptions::print_assets` has a tile for it
+                (true, 2) => vec![desc.print_face(PrintFace::Top).print_dropped(!blank)],
+                // Signs get their print as a decal standing off the front face
+                (true, _) => vec![
+                    desc.print_face(PrintFace::Front),
+                    BrickDesc::new("PB_DefaultTile")
+                        .size((z, length * 5, 1))
+                        .offset((width as i32 * 5 + 1, 0, 0))
+                        .rotation_offset(0)
+                        .direction_override(XPositive)
+                        .decal(true),
+                ],
+            })
         },
 
-        // TODO: Remove (?: Print)? when prints exist
-        r"^(-)?(25|45|65|72|80)° ?(Inv )?Ramp(?: (\d+)x)?( Corner)?(?: Print)?$" => |captures, _| {
-            let neg = captures.get(1).is_some();
-            let inv = captures.get(3).is_some();
-            let corner = captures.get(5).is_some();
+        // Brickadia ramps can't show prints, so they're only kept in the report. Add-on
+        // packs also name ramps by footprint, as in `Ramp 1x6`, and scale corners, as in
+        // `25° Ramp Corner 2x`
+        r"^(?P<neg>-)?(?:(?P<angle>25|45|65|72|80)° ?)?(?P<inv>Inv )?Ramp(?: (?P<length>\d+)x| (?P<width>\d+)x(?P<depth>\d+))?(?P<corner> Corner(?: (?P<scale>\d+)x)?)?(?P<print> Print)?$" => |captures, from| {
+            let neg = captures.name("neg").is_some();
+            let inv = captures.name("inv").is_some();
+            let corner = captures.name("corner").is_some();
+            let print = captures.name("print").is_some();
+            let footprint = captures.name("width").zip(captures.name("depth"));
 
-            if inv && !corner {
-                return None;
-            }
-
-            let asset = if neg {
+            let asset = if inv && !corner {
+                // A straight ramp has no inner variant, so this is just the ceiling slope
+                "PB_DefaultRampInverted"
+            } else if neg {
                 if inv {
                     "PB_DefaultRampInnerCornerInverted"
                 } else if corner {
@@ -588,40 +797,92 @@ lazy_static! {
                 "PB_DefaultRamp"
             };
 
-            let degree_str = captures.get(2).unwrap().as_str();
-
-            let (x, z) = if degree_str == "25" {
-                (15, 6)
-            } else if degree_str == "45" {
-                (10, 6)
-            } else if degree_str == "65" {
-                (10, 12)
-            } else if degree_str == "72" {
-                (10, 18)
-            } else if degree_str == "80" {
-                (10, 30)
-            } else {
-                return None;
+            // The depth and height depend only on the angle, so `72° Ramp 4x` and
+            // `-80° Ramp 6x` are as tall as their 1x versions
+            let angle = captures.name("angle").map(|angle| angle.as_str());
+            let (mut x, mut z) = match angle {
+                Some("25") => (15, 6),
+                Some("45") | None => (10, 6),
+                Some("65") => (10, 12),
+                Some("72") => (10, 18),
+                Some("80") => (10, 30),
+                _ => return None,
             };
 
             let mut y = x;
+            let mut approximate = false;
 
-            if let Some(group) = captures.get(4) {
-                if corner {
+            if let Some(group) = captures.name("length") {
+                // Only the angled ramps come in long versions
+                if corner || angle.is_none() {
                     return None;
                 }
 
                 let length: u32 = group.as_str().parse().ok()?;
+                if length == 0 {
+                    return None;
+                }
                 y = length * 5;
+            } else if let Some((width, depth)) = footprint {
+                let width: u32 = width.as_str().parse().ok()?;
+                let depth: u32 = depth.as_str().parse().ok()?;
+                if corner || width == 0 || depth == 0 {
+                    return None;
+                }
+
+                // Ramps with an angle keep its slope, the others rise one brick, in whole
+                // plates either way
+                if angle.is_some() {
+                    z = ((z * depth * 5 + x) / (2 * x)).max(1) * 2;
+                }
+                x = depth * 5;
+                y = width * 5;
+                approximate = true;
+            } else if angle.is_none() {
+                return None;
+            }
+
+            if let Some(group) = captures.name("scale") {
+                let scale: u32 = group.as_str().parse().ok()?;
+                if scale == 0 {
+                    return None;
+                }
+                x *= scale;
+                y *= scale;
+                z *= scale;
+                approximate = true;
+            }
+
+            // Except for straight road ramps, whose lanes and stripes can be split out
+            if print && angle == Some("45") && footprint.is_none() && asset == "PB_DefaultRamp" && is_road_print(&from.base.print) {
+                return Some(road_ramp((x, y, z), &from.base.print));
             }
 
-            Some(vec![BrickDesc::new(asset).size((x, y, z)).rotation_offset(0)])
+            let desc = BrickDesc::new(asset).size((x, y, z)).rotation_offset(0).print_dropped(print).approximate(approximate);
+            Some(vec![if print { desc.print_face(PrintFace::Slope) } else { desc }])
         },
 
-        r"(?P<angle>25|45)° Crest (?:(?P<end>End)|(?P<corner>Corner)|(?P<length>\d+)x)" => |captures, _| {
+        // Road corners are printed wedges; other prints can't be shown on the slope
+        r"^(?:(?P<small>1x1)|2x2) Wedge Print$" => |captures, from| {
+            let small = captures.name("small").is_some();
+            if is_road_print(&from.base.print) {
+                let road = if small { ROAD_WEDGE_1X1 } else { ROAD_WEDGE_2X2 };
+                return Some(road.iter().map(|desc| desc.clone().approximate(true)).collect());
+            }
+
+            let size = if small { (5, 5, 6) } else { (10, 10, 6) };
+            Some(vec![BrickDesc::new("PB_DefaultSideWedge").size(size).rotation_offset(0)
+                .print_dropped(true).print_face(PrintFace::Slope)])
+        },
+
+        r"(?P<angle>25|45|65|72|80)° Crest (?:(?P<end>End)|(?P<corner>Corner)|(?P<length>\d+)x)" => |captures, _| {
+            // Steeper crests are taller in Blockland, the same as the ramps
             let (z, offset) = match captures.name("angle").unwrap().as_str() {
-                s if s == "25" => (4, -2),
-                s if s == "45" => (6, 0),
+                "25" => (4, -2),
+                "45" => (6, 0),
+                "65" => (12, 0),
+                "72" => (18, 0),
+                "80" => (30, 0),
                 _ => return None,
             };
 
@@ -712,7 +973,7 @@ lazy_static! {
                 .inverted_modter_rotate(imr)
                 .direction_override(direction)])
         },
-        r"(\d+)x(\d+)x?(?P<height>\d+)? Arch(?P<up> Up)?" => |captures, _| {
+        r"^(\d+)x(\d+)x?(?P<height>\d+)? Arch(?P<half> Half)?(?P<up> Up)?$" => |captures, _| {
             let width: u32 = captures.get(1).unwrap().as_str().parse().ok()?;
             let length: u32 = captures.get(2).unwrap().as_str().parse().ok()?;
             let height: u32 = if captures.name("height").is_some() {
@@ -726,6 +987,23 @@ lazy_static! {
             };
             let up = captures.name("up").is_some();
             let direction = if up { ZNegative } else { ZPositive };
+
+            if captures.name("half").is_some() {
+                // Approximate the quarter circle with a wedge under a plate
+                let z = height * 6;
+                if z <= 2 {
+                    return None;
+                }
+                let top_offset = if up { -(z as i32 - 2) } else { z as i32 - 2 };
+                return Some(vec![
+                    BrickDesc::new("PB_DefaultBrick").size((length * 5, width * 5, 2)).rotation_offset(0)
+                        .offset((0, 0, top_offset)).direction_override(direction).approximate(true),
+                    BrickDesc::new("PB_DefaultWedge").size((length * 5, width * 5, z - 2)).rotation_offset(0)
+                        .offset((0, 0, if up { 2 } else { -2 })).direction_override(direction)
+                        .inverted_wedge_rotate(up).approximate(true),
+                ]);
+            }
+
             Some(vec![BrickDesc::new("PB_DefaultArch")
                 .size((width * 5, length * 5, height * 6))
                 .direction_override(direction)
@@ -755,6 +1033,182 @@ lazy_static! {
                 ])
             }
         },
+        // Pole add-ons, thin rods centered on a 1x1 footprint
+        r"^1x1(?:x(?P<height>\d+)|(?P<plate>[Ff]))? Pole$" => |captures, _| {
+            let z: u32 = if captures.name("plate").is_some() {
+                2
+            } else {
+                captures
+                    .name("height")
+                    .map(|g| g.as_str().parse::<u32>().ok())
+                    .unwrap_or(Some(1))?
+                    * 6
+            };
+            Some(vec![BrickDesc::new("PB_DefaultPole").size((2, 2, z))])
+        },
+        // Water is only for looks in Brickadia, so it can be walked through
+        WATER_PATTERN => |captures, _| {
+            let parse = |name| {
+                captures
+                    .name(name)
+                    .map(|g| g.as_str().parse::<u32>().ok())
+                    .unwrap_or(Some(1))
+            };
+            let width = parse("width")?;
+            let length = parse("length")?;
+            let z = if captures.name("cube").is_some() {
+                width * 5
+            } else if captures.name("plate").is_some() {
+                2
+            } else {
+                parse("height")? * 6
+            };
+            if width == 0 || length == 0 || z == 0 {
+                return None;
+            }
+
+            Some(vec![BrickDesc::new("PB_DefaultSmoothTile")
+                .size((width * 5, length * 5, z))
+                .color_override(WATER_COLOR)
+                .collision_override(false)
+                .approximate(true)])
+        },
+        // A plate in the color of the kind, with a flag on a pole in the middle. Shown even
+        // if the brick was hidden, as minigame bricks often are, so it can be found
+        MINIGAME_PATTERN => |captures, from| {
+            let studs = |name| match captures.name(name) {
+                Some(group) => group.as_str().parse::<u32>().ok(),
+                None => Some(2),
+            };
+            let width = studs("width")?;
+            let length = studs("length")?;
+            if width == 0 || length == 0 {
+                return None;
+            }
+            let color = match minigame_kind(&from.base.ui_name)? {
+                MinigameKind::Checkpoint => [0, 200, 60, 255],
+                MinigameKind::CapturePoint => [220, 30, 30, 255],
+                MinigameKind::Spawn => [240, 200, 0, 255],
+            };
+
+            Some(vec![
+                BrickDesc::new("PB_DefaultBrick").size((width * 5, length * 5, 2)).color_override(color).visibility_override(true).approximate(true),
+                BrickDesc::new("PB_DefaultPole").size((1, 1, 15)).offset((0, 0, 17)).color_override([230, 230, 230, 255]).visibility_override(true).approximate(true),
+                BrickDesc::new("PB_DefaultMicroBrick").size((4, 1, 3)).offset((5, 0, 29)).rotation_offset(0).color_override(color).visibility_override(true).approximate(true),
+            ])
+        },
+        // A pad in the frame color with a see-through portal inset on top
+        TELEPORTER_PATTERN => |captures, _| {
+            let studs = |name| match captures.name(name) {
+                Some(group) => group.as_str().parse::<u32>().ok(),
+                None => Some(2),
+            };
+            let width = studs("width")?;
+            let length = studs("length")?;
+            if width == 0 || length == 0 {
+                return None;
+            }
+            let z = if captures.name("plate").is_some() || captures.name("width").is_none() { 2 } else { 6 };
+
+            Some(vec![
+                BrickDesc::new("PB_DefaultBrick").size((width * 5, length * 5, z - 1)).offset((0, 0, -1)).color_override(PORTAL_FRAME_COLOR).approximate(true),
+                BrickDesc::new("PB_DefaultTile").size((width * 5 - 1, length * 5 - 1, 1)).offset((0, 0, z as i32 - 1)).color_override(PORTAL_COLOR).approximate(true),
+            ])
+        },
+        // Fence and railing add-ons, laid out along the length like `1x4x2 Bars`
+        r"^1x(?P<length>\d+)(?:x(?P<height>\d+))? (?:(?P<railing>Railing)|(?P<wall>Wall Fence)|Lattice(?: Fence)?)$" => |captures, _| {
+            let railing = captures.name("railing").is_some();
+            let wall = captures.name("wall").is_some();
+            let length: u32 = captures.name("length").unwrap().as_str().parse().ok()?;
+            let height: u32 = captures
+                .name("height")
+                .map(|g| g.as_str().parse::<u32>().ok())
+                .unwrap_or(Some(if railing { 1 } else { 2 }))?;
+            if length == 0 || height == 0 {
+                return None;
+            }
+
+            let z = height * 6;
+            let top = z as i32;
+            let stud_offsets = (0..length).map(|i| (i * 10) as i32 - (length * 5) as i32 + 5);
+            let mut descs = vec![
+                BrickDesc::new("PB_DefaultMicroBrick").size((5, length * 5, 1)).offset((0, 0, top - 1)),
+            ];
+
+            if railing {
+                // Posts at the ends only
+                let end = (length * 5) as i32 - 5;
+                descs.push(BrickDesc::new("PB_DefaultPole").size((2, 2, z - 1)).offset((0, -end, -1)));
+                if end != 0 {
+                    descs.push(BrickDesc::new("PB_DefaultPole").size((2, 2, z - 1)).offset((0, end, -1)));
+                }
+            } else if wall {
+                // Solid lower half with bars above
+                descs.push(BrickDesc::new("PB_DefaultBrick").size((5, length * 5, z / 2)).offset((0, 0, -top / 2)));
+                descs.extend(stud_offsets.map(|y| {
+                    BrickDesc::new("PB_DefaultPole").size((2, 2, z / 2 - 1)).offset((0, y, top / 2 - 1))
+                }));
+            } else {
+                // The diagonal lattice is approximated with a grid of thin rods
+                descs.push(BrickDesc::new("PB_DefaultMicroBrick").size((5, length * 5, 2)).offset((0, 0, 2 - top)));
+                descs.push(BrickDesc::new("PB_DefaultMicroBrick").size((1, length * 5, 1)).offset((0, 0, -1)));
+                descs.extend(stud_offsets.map(|y| {
+                    BrickDesc::new("PB_DefaultPole").size((1, 1, z - 3)).offset((0, y, -1))
+                }));
+            }
+
+            Some(descs.into_iter().map(|desc| desc.approximate(true)).collect())
+        },
+        // Ladders, as two rails with a rung every brick of height. Brickadia can't climb
+        // them, but they keep their look
+        r"^(?:1x(?P<width>\d+)x(?P<height>\d+) )?Ladder$" => |captures, _| {
+            let parse = |name, default| {
+                captures
+                    .name(name)
+                    .map(|g| g.as_str().parse::<u32>().ok())
+                    .unwrap_or(Some(default))
+            };
+            let width = parse("width", 2)?;
+            let height = parse("height", 5)?;
+            if width == 0 || height == 0 {
+                return None;
+            }
+
+            let z = height * 6;
+            let rail = (width * 5) as i32 - 1;
+            let mut descs = vec![
+                BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, z)).offset((0, -rail, 0)),
+                BrickDesc::new("PB_DefaultMicroBrick").size((1, 1, z)).offset((0, rail, 0)),
+            ];
+            descs.extend((0..height).map(|i| {
+                BrickDesc::new("PB_DefaultMicroBrick")
+                    .size((1, width * 5 - 2, 1))
+                    .offset((0, 0, (i * 12) as i32 + 6 - z as i32))
+            }));
+
+            Some(descs.into_iter().map(|desc| desc.approximate(true)).collect())
+        },
+        // Flagpoles, a 1x1 plate with a thin pole standing on it, or hanging from it
+        r"^(?:1x1x(?P<height>\d+) )?Flag ?[Pp]ole(?P<inv> Inv)?$" => |captures, _| {
+            let height: u32 = captures
+                .name("height")
+                .map(|g| g.as_str().parse::<u32>().ok())
+                .unwrap_or(Some(10))?;
+            if height == 0 {
+                return None;
+            }
+
+            let z = height * 6;
+            let inv = captures.name("inv").is_some();
+            let (direction, sign) = if inv { (ZNegative, -1) } else { (ZPositive, 1) };
+
+            Some(vec![
+                BrickDesc::new("PB_DefaultBrick").size((5, 5, 2)).offset((0, 0, sign * (2 - z as i32)))
+                    .direction_override(direction).approximate(true),
+                BrickDesc::new("PB_DefaultPole").size((2, 2, z - 2)).offset((0, 0, sign * 2))
+                    .direction_override(direction).approximate(true),
+            ])
+        },
         // 1RandomPack Center Ramps
         r"^(-)?(\d+)° Center (Diag )?Ramp 1x" => |captures, _| {
             let neg = captures.get(1).is_some();
@@ -788,4 +1242,9 @@ lazy_static! {
             ])
         },
     ];
+
+    /// All of `BRICK_MAP_REGEX` at once, to find the first match in a single pass.
+    pub static ref BRICK_MAP_REGEX_SET: RegexSet =
+        RegexSet::new(BRICK_MAP_REGEX.iter().map(|(regex, _)| regex.as_str()))
+            .expect("failed to compile regex set");
 }
@@ -9,15 +9,6 @@ macro_rules! map {
     }
 }
 
-// #[macro_export]
-macro_rules! brick_map_literal {
-    [$($ui:expr => $map:expr),* $(,)?] => {
-        map![
-            $($ui => $map.into(),)*
-        ]
-    }
-}
-
 // #[macro_export]
 macro_rules! brick_map_regex {
     [$($source:expr => $func:expr),* $(,)?] => {
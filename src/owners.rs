@@ -0,0 +1,103 @@
+//! Mapping Blockland BL_IDs to Brickadia users.
+
+use brs::uuid::Uuid;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct OwnerMap {
+    pub owners: HashMap<u32, brs::User>,
+    pub fallback: OwnerFallback,
+}
+
+/// What to do with bricks whose BL_ID isn't in the owner map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OwnerFallback {
+    /// Leave them public.
+    #[default]
+    Public,
+    /// Create a placeholder user per BL_ID, see `generated_user`.
+    Generate,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OwnersFile {
+    #[serde(default)]
+    fallback: Option<String>,
+    #[serde(default)]
+    owners: HashMap<String, OwnerEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OwnerEntry {
+    name: String,
+    #[serde(default)]
+    id: Option<String>,
+}
+
+impl OwnerMap {
+    /// Parses an owners file:
+    ///
+    /// ```toml
+    /// fallback = "generate" # or "public"
+    ///
+    /// [owners.12345]
+    /// name = "Zeblote"
+    /// id = "a1b16aca-9627-4a16-a160-67fa9adbb7b6" # optional
+    /// ```
+    pub fn from_toml(source: &str) -> Result<Self, String> {
+        let file: OwnersFile = toml::from_str(source).map_err(|e| e.to_string())?;
+
+        let fallback = match file.fallback.as_deref() {
+            None | Some("public") => OwnerFallback::Public,
+            Some("generate") => OwnerFallback::Generate,
+            Some(other) => return Err(format!("unknown fallback {:?}", other)),
+        };
+
+        let mut owners = HashMap::with_capacity(file.owners.len());
+
+        for (bl_id, entry) in file.owners {
+            let bl_id: u32 = bl_id
+                .parse()
+                .map_err(|_| format!("invalid BL_ID {:?}", bl_id))?;
+
+            let id = match entry.id {
+                Some(id) => Uuid::parse_str(&id)
+                    .map_err(|e| format!("invalid id for BL_ID {}: {}", bl_id, e))?,
+                None => generated_user(bl_id).id,
+            };
+
+            owners.insert(
+                bl_id,
+                brs::User {
+                    id,
+                    name: entry.name,
+                },
+            );
+        }
+
+        Ok(Self { owners, fallback })
+    }
+
+    /// The user that should own bricks planted by `bl_id`, or `None` for public.
+    pub fn user(&self, bl_id: u32) -> Option<brs::User> {
+        match self.owners.get(&bl_id) {
+            Some(user) => Some(user.clone()),
+            None if self.fallback == OwnerFallback::Generate => Some(generated_user(bl_id)),
+            None => None,
+        }
+    }
+}
+
+/// A stable placeholder user for a BL_ID, so separately converted saves agree.
+pub fn generated_user(bl_id: u32) -> brs::User {
+    let mut bytes = *b"bls2brs\0\0\0\0\0\0\0\0\0";
+    bytes[12..].copy_from_slice(&bl_id.to_be_bytes());
+
+    brs::User {
+        id: Uuid::from_bytes(bytes),
+        name: format!("BL_ID {}", bl_id),
+    }
+}
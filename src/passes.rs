@@ -0,0 +1,315 @@
+//! Post-processing over converted bricks.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+type BrickKey = (
+    u32,
+    (i32, i32, i32),
+    (u32, u32, u32),
+    u8,
+    u8,
+    (u8, u32),
+    u32,
+);
+
+fn brick_key(brick: &brs::Brick) -> BrickKey {
+    let color = match &brick.color {
+        brs::ColorMode::Set(index) => (0, *index),
+        brs::ColorMode::Custom(c) => (1, u32::from_le_bytes([c.r(), c.g(), c.b(), c.a()])),
+    };
+
+    (
+        brick.asset_name_index,
+        brick.position,
+        brick.size,
+        brick.direction as u8,
+        brick.rotation as u8,
+        color,
+        brick.material_index,
+    )
+}
+
+/// Finds bricks identical in asset, position, size, orientation, color and material to
+/// an earlier brick. Returns which bricks to keep, see `retain_mask`.
+pub fn dedup(bricks: &[brs::Brick]) -> Vec<bool> {
+    let mut seen = HashSet::with_capacity(bricks.len());
+    bricks
+        .iter()
+        .map(|brick| seen.insert(brick_key(brick)))
+        .collect()
+}
+
+/// Finds the bricks at `candidates` (sorted indices) that rest on the lowest level of the
+/// build. Returns which bricks to keep, see `retain_mask`.
+pub fn strip_baseplates(bricks: &[brs::Brick], candidates: &[usize]) -> Vec<bool> {
+    let mut keep = vec![true; bricks.len()];
+
+    let lowest = match bricks.iter().map(|brick| brick_bounds(brick).0[2]).min() {
+        Some(lowest) => lowest,
+        None => return keep,
+    };
+
+    for &index in candidates {
+        if brick_bounds(&bricks[index]).0[2] == lowest {
+            keep[index] = false;
+        }
+    }
+
+    keep
+}
+
+/// Moves bricks by up to `tolerance` units on each axis so their lower edge lines up with
+/// a grid of `grid` units. Only axes where the brick spans whole grid cells are moved, so
+/// micro bricks and poles keep their place. Returns how many bricks were moved and the
+/// total distance over all axes.
+pub fn snap_to_grid(bricks: &mut [brs::Brick], grid: [i64; 3], tolerance: i64) -> (usize, u64) {
+    let mut count = 0;
+    let mut total = 0;
+
+    for brick in bricks {
+        let (min, max) = brick_bounds(brick);
+        let mut delta = [0; 3];
+
+        for axis in 0..3 {
+            if (max[axis] - min[axis]) % grid[axis] != 0 {
+                continue;
+            }
+            let remainder = min[axis].rem_euclid(grid[axis]);
+            let to_grid = if remainder * 2 < grid[axis] {
+                -remainder
+            } else {
+                grid[axis] - remainder
+            };
+            if to_grid.abs() <= tolerance {
+                delta[axis] = to_grid;
+            }
+        }
+
+        if delta == [0; 3] {
+            continue;
+        }
+
+        let (x, y, z) = brick.position;
+        brick.position = (
+            x + delta[0] as i32,
+            y + delta[1] as i32,
+            z + delta[2] as i32,
+        );
+        count += 1;
+        total += delta.iter().map(|d| d.unsigned_abs()).sum::<u64>();
+    }
+
+    (count, total)
+}
+
+/// Merges cubes on a shared lattice, as in Blockland cube terrain, into larger bricks of
+/// at most `max_side` units per side. Only bricks whose asset is marked in `resizable`
+/// and that match in everything but position are merged. The first brick of each merged
+/// box is resized to cover it, and the rest are dropped. Returns which bricks to keep,
+/// see `retain_mask`.
+pub fn merge_cubes(bricks: &mut [brs::Brick], resizable: &[bool], max_side: i64) -> Vec<bool> {
+    let mut keep = vec![true; bricks.len()];
+    let mut lattices: HashMap<_, HashMap<[i64; 3], usize>> = HashMap::new();
+
+    for (index, brick) in bricks.iter().enumerate() {
+        let (sx, sy, sz) = brick.size;
+        if sx != sy
+            || sy != sz
+            || sx == 0
+            || !resizable
+                .get(brick.asset_name_index as usize)
+                .copied()
+                .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let step = i64::from(sx) * 2;
+        let (x, y, z) = brick.position;
+        let position = [i64::from(x), i64::from(y), i64::from(z)];
+        let phase = position.map(|u| u.rem_euclid(step));
+        // Cubes look the same at any rotation
+        let (asset, _, size, direction, _, color, material) = brick_key(brick);
+        let key = (
+            (asset, size, direction, color, material),
+            brick.collision,
+            brick.visibility,
+            brick.owner_index,
+            phase,
+        );
+        let cell = position.map(|u| u.div_euclid(step));
+        lattices.entry(key).or_default().insert(cell, index);
+    }
+
+    for cells in lattices.into_values() {
+        let step = i64::from(bricks[*cells.values().next().unwrap()].size.0) * 2;
+        let max_cells = (max_side / step).max(1);
+        let mut order: Vec<[i64; 3]> = cells.keys().copied().collect();
+        order.sort_by_key(|c| (c[2], c[1], c[0]));
+        let mut taken: HashSet<[i64; 3]> = HashSet::with_capacity(order.len());
+
+        let free =
+            |taken: &HashSet<[i64; 3]>, c: [i64; 3]| cells.contains_key(&c) && !taken.contains(&c);
+
+        for start in order {
+            if taken.contains(&start) {
+                continue;
+            }
+
+            // Grow along X, then whole rows along Y, then whole layers along Z
+            let mut len = [1, 1, 1];
+            while len[0] < max_cells && free(&taken, [start[0] + len[0], start[1], start[2]]) {
+                len[0] += 1;
+            }
+            while len[1] < max_cells
+                && (0..len[0]).all(|dx| free(&taken, [start[0] + dx, start[1] + len[1], start[2]]))
+            {
+                len[1] += 1;
+            }
+            while len[2] < max_cells
+                && (0..len[0]).all(|dx| {
+                    (0..len[1])
+                        .all(|dy| free(&taken, [start[0] + dx, start[1] + dy, start[2] + len[2]]))
+                })
+            {
+                len[2] += 1;
+            }
+
+            for dz in 0..len[2] {
+                for dy in 0..len[1] {
+                    for dx in 0..len[0] {
+                        let cell = [start[0] + dx, start[1] + dy, start[2] + dz];
+                        taken.insert(cell);
+                        keep[cells[&cell]] = false;
+                    }
+                }
+            }
+
+            let first = cells[&start];
+            keep[first] = true;
+            if len == [1, 1, 1] {
+                continue;
+            }
+
+            let brick = &mut bricks[first];
+            let half = step / 2;
+            let (x, y, z) = brick.position;
+            brick.position = (
+                x + ((len[0] - 1) * half) as i32,
+                y + ((len[1] - 1) * half) as i32,
+                z + ((len[2] - 1) * half) as i32,
+            );
+            brick.size = (
+                (len[0] * half) as u32,
+                (len[1] * half) as u32,
+                (len[2] * half) as u32,
+            );
+            brick.rotation = brs::Rotation::Deg0;
+        }
+    }
+
+    keep
+}
+
+/// Removes the items whose entry in `keep` is false, returning how many were removed.
+/// Items past the end of `keep` are kept, so this does nothing to an empty list.
+pub fn retain_mask<T>(items: &mut Vec<T>, keep: &[bool]) -> usize {
+    let before = items.len();
+    let mut keep = keep.iter();
+    items.retain(|_| keep.next().copied().unwrap_or(true));
+    before - items.len()
+}
+
+/// Horizontal extent and lowest point of all bricks, as `(min, max)` X/Y and bottom Z.
+pub(crate) fn footprint(bricks: &[brs::Brick]) -> Option<([i64; 2], [i64; 2], i64)> {
+    let mut bricks = bricks.iter().map(brick_bounds);
+    let (min, max) = bricks.next()?;
+    let mut footprint = ([min[0], min[1]], [max[0], max[1]], min[2]);
+
+    for (min, max) in bricks {
+        footprint.0[0] = footprint.0[0].min(min[0]);
+        footprint.0[1] = footprint.0[1].min(min[1]);
+        footprint.1[0] = footprint.1[0].max(max[0]);
+        footprint.1[1] = footprint.1[1].max(max[1]);
+        footprint.2 = footprint.2.min(min[2]);
+    }
+
+    Some(footprint)
+}
+
+/// Two output bricks whose bounding boxes intersect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Overlap {
+    /// Indices into `WriteData::bricks`.
+    pub bricks: (usize, usize),
+    pub positions: ((i32, i32, i32), (i32, i32, i32)),
+}
+
+pub(crate) type Bounds = ([i64; 3], [i64; 3]);
+
+/// Approximate world-space bounding box of a brick, from its half-extents and orientation.
+pub(crate) fn brick_bounds(brick: &brs::Brick) -> Bounds {
+    use brs::Direction::*;
+
+    let (sx, sy, sz) = brick.size;
+    let odd = brick.rotation as u8 % 2 == 1;
+    let (ex, ey, ez) = match brick.direction {
+        ZPositive | ZNegative if odd => (sy, sx, sz),
+        ZPositive | ZNegative => (sx, sy, sz),
+        XPositive | XNegative if odd => (sz, sx, sy),
+        XPositive | XNegative => (sz, sy, sx),
+        YPositive | YNegative if odd => (sy, sz, sx),
+        YPositive | YNegative => (sx, sz, sy),
+    };
+
+    let (x, y, z) = brick.position;
+    let center = [i64::from(x), i64::from(y), i64::from(z)];
+    let extent = [i64::from(ex), i64::from(ey), i64::from(ez)];
+
+    (
+        [
+            center[0] - extent[0],
+            center[1] - extent[1],
+            center[2] - extent[2],
+        ],
+        [
+            center[0] + extent[0],
+            center[1] + extent[1],
+            center[2] + extent[2],
+        ],
+    )
+}
+
+/// Finds all pairs of bricks with intersecting bounding boxes. Touching faces don't count.
+pub fn find_overlaps(bricks: &[brs::Brick]) -> Vec<Overlap> {
+    let bounds: Vec<Bounds> = bricks.iter().map(brick_bounds).collect();
+
+    // Sweep along X, keeping the bricks whose X range is still open
+    let mut order: Vec<usize> = (0..bricks.len()).collect();
+    order.sort_by_key(|&i| bounds[i].0[0]);
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut overlaps = Vec::new();
+
+    for i in order {
+        let (min, max) = bounds[i];
+        active.retain(|&j| bounds[j].1[0] > min[0]);
+
+        for &j in &active {
+            let (other_min, other_max) = bounds[j];
+            if (1..3).all(|axis| min[axis] < other_max[axis] && other_min[axis] < max[axis]) {
+                let pair = (j.min(i), j.max(i));
+                overlaps.push(Overlap {
+                    bricks: pair,
+                    positions: (bricks[pair.0].position, bricks[pair.1].position),
+                });
+            }
+        }
+
+        active.push(i);
+    }
+
+    overlaps.sort_by_key(|o| o.bricks);
+    overlaps
+}
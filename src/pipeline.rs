@@ -0,0 +1,229 @@
+//! Post-processing passes that run in a configurable order after conversion.
+//!
+//! The built-in passes are named for `Pipeline::from_specs`:
+//!
+//! - `dedup`, see `ConvertOptions::dedup`
+//! - `merge-terrain`, see `ConvertOptions::merge_terrain`
+//! - `snap=TOLERANCE`, see `ConvertOptions::grid_snap`
+//! - `recenter`, moves the build over the origin with its lowest point at zero
+//! - `clip=X0,Y0,Z0,X1,Y1,Z1`, keeps only the bricks entirely inside this box
+
+use crate::{passes, ConvertOptions, Provenance, Scale, MAX_SIDE, RESIZABLE_ASSETS};
+
+/// A step over a converted save. Passes may change bricks in place and say which to
+/// remove, so `Pipeline` can keep `ConvertReport::provenance` in step.
+pub trait Pass {
+    /// Used in `ConvertReport::pipeline_removed` and logs.
+    fn name(&self) -> &str;
+
+    /// Changes `data`, returning which of its bricks to keep, see `passes::retain_mask`.
+    /// An empty list keeps every brick.
+    fn run(&self, data: &mut brs::WriteData, options: &ConvertOptions) -> Vec<bool>;
+}
+
+/// Passes run in order after the ones enabled by `ConvertOptions` flags.
+#[derive(Default)]
+pub struct Pipeline {
+    passes: Vec<Box<dyn Pass + Send + Sync>>,
+}
+
+impl Pipeline {
+    /// Builds a pipeline of built-in passes from their names, see the module docs.
+    pub fn from_specs(specs: &[impl AsRef<str>]) -> Result<Self, String> {
+        let mut pipeline = Self::default();
+        for spec in specs {
+            pipeline.passes.push(parse_spec(spec.as_ref())?);
+        }
+        Ok(pipeline)
+    }
+
+    pub fn push(&mut self, pass: impl Pass + Send + Sync + 'static) {
+        self.passes.push(Box::new(pass));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Runs every pass, returning how many bricks each one removed.
+    pub(crate) fn run(
+        &self,
+        data: &mut brs::WriteData,
+        options: &ConvertOptions,
+        provenance: &mut Vec<Provenance>,
+    ) -> Vec<(String, usize)> {
+        self.passes
+            .iter()
+            .map(|pass| {
+                let removed = apply(pass.as_ref(), data, options, provenance);
+                log::debug!("{} pass removed {} bricks", pass.name(), removed);
+                (pass.name().to_string(), removed)
+            })
+            .collect()
+    }
+}
+
+/// Runs one pass, removing the bricks and provenance it drops. Returns how many it removed.
+pub(crate) fn apply(
+    pass: &(impl Pass + ?Sized),
+    data: &mut brs::WriteData,
+    options: &ConvertOptions,
+    provenance: &mut Vec<Provenance>,
+) -> usize {
+    let keep = pass.run(data, options);
+    passes::retain_mask(provenance, &keep);
+    passes::retain_mask(&mut data.bricks, &keep)
+}
+
+fn parse_spec(spec: &str) -> Result<Box<dyn Pass + Send + Sync>, String> {
+    let (name, value) = match spec.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (spec, None),
+    };
+
+    match (name, value) {
+        ("dedup", None) => Ok(Box::new(Dedup)),
+        ("merge-terrain", None) => Ok(Box::new(MergeTerrain)),
+        ("recenter", None) => Ok(Box::new(Recenter)),
+        ("snap", Some(tolerance)) => {
+            let tolerance = tolerance
+                .parse()
+                .map_err(|_| format!("invalid tolerance for snap: {:?}", tolerance))?;
+            Ok(Box::new(Snap { tolerance }))
+        }
+        ("clip", Some(bounds)) => {
+            let bounds: Vec<i64> = bounds
+                .split(',')
+                .map(|u| u.trim().parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("invalid box for clip: {:?}", bounds))?;
+            match bounds[..] {
+                [x0, y0, z0, x1, y1, z1] => Ok(Box::new(Clip {
+                    min: [x0.min(x1), y0.min(y1), z0.min(z1)],
+                    max: [x0.max(x1), y0.max(y1), z0.max(z1)],
+                })),
+                _ => Err(format!("clip needs 6 numbers, got {}", bounds.len())),
+            }
+        }
+        ("snap", None) | ("clip", None) => Err(format!("{} pass needs a value", name)),
+        (_, Some(_)) if ["dedup", "merge-terrain", "recenter"].contains(&name) => {
+            Err(format!("{} pass takes no value", name))
+        }
+        _ => Err(format!("unknown pass {:?}", name)),
+    }
+}
+
+pub struct Dedup;
+
+impl Pass for Dedup {
+    fn name(&self) -> &str {
+        "dedup"
+    }
+
+    fn run(&self, data: &mut brs::WriteData, _: &ConvertOptions) -> Vec<bool> {
+        passes::dedup(&data.bricks)
+    }
+}
+
+pub struct MergeTerrain;
+
+impl Pass for MergeTerrain {
+    fn name(&self) -> &str {
+        "merge-terrain"
+    }
+
+    fn run(&self, data: &mut brs::WriteData, _: &ConvertOptions) -> Vec<bool> {
+        let resizable: Vec<bool> = data
+            .brick_assets
+            .iter()
+            .map(|asset| RESIZABLE_ASSETS.contains(&asset.as_str()))
+            .collect();
+        passes::merge_cubes(&mut data.bricks, &resizable, MAX_SIDE)
+    }
+}
+
+pub struct Snap {
+    /// In units, on each axis.
+    pub tolerance: u32,
+}
+
+impl Snap {
+    /// Runs the pass, returning how many bricks moved and how far in total.
+    pub(crate) fn snap(&self, data: &mut brs::WriteData, scale: Scale) -> (usize, u64) {
+        let grid = match scale {
+            Scale::Normal => [10, 10, 4],
+            Scale::Micro => [2, 2, 1],
+        };
+        passes::snap_to_grid(&mut data.bricks, grid, i64::from(self.tolerance))
+    }
+}
+
+impl Pass for Snap {
+    fn name(&self) -> &str {
+        "snap"
+    }
+
+    fn run(&self, data: &mut brs::WriteData, options: &ConvertOptions) -> Vec<bool> {
+        self.snap(data, options.scale);
+        Vec::new()
+    }
+}
+
+pub struct Recenter;
+
+impl Pass for Recenter {
+    fn name(&self) -> &str {
+        "recenter"
+    }
+
+    fn run(&self, data: &mut brs::WriteData, options: &ConvertOptions) -> Vec<bool> {
+        let (min, max, bottom) = match passes::footprint(&data.bricks) {
+            Some(footprint) => footprint,
+            None => return Vec::new(),
+        };
+
+        // Shift by whole studs, so bricks stay on the grid
+        let stud = match options.scale {
+            Scale::Normal => 10,
+            Scale::Micro => 2,
+        };
+        let to_stud = |u: i64| u.div_euclid(stud) * stud;
+        let shift = [
+            to_stud((min[0] + max[0]) / 2),
+            to_stud((min[1] + max[1]) / 2),
+            bottom,
+        ];
+
+        for brick in &mut data.bricks {
+            let (x, y, z) = brick.position;
+            brick.position = (
+                x - shift[0] as i32,
+                y - shift[1] as i32,
+                z - shift[2] as i32,
+            );
+        }
+
+        Vec::new()
+    }
+}
+
+pub struct Clip {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+impl Pass for Clip {
+    fn name(&self) -> &str {
+        "clip"
+    }
+
+    fn run(&self, data: &mut brs::WriteData, _: &ConvertOptions) -> Vec<bool> {
+        data.bricks
+            .iter()
+            .map(|brick| {
+                let (min, max) = passes::brick_bounds(brick);
+                (0..3).all(|axis| min[axis] >= self.min[axis] && max[axis] <= self.max[axis])
+            })
+            .collect()
+    }
+}
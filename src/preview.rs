@@ -0,0 +1,139 @@
+//! Top-down preview images of converted saves, and of their palettes.
+
+use crate::{passes::brick_bounds, resolve_color};
+use std::io::{self, Write};
+
+/// Writes a top-down orthographic PNG of `data`, at most `max_size` pixels wide or tall.
+///
+/// Every brick is drawn as a rectangle of its footprint, higher bricks on top. X points
+/// right and Y points down in the image.
+pub fn write_png(w: impl Write, data: &brs::WriteData, max_size: u32) -> io::Result<()> {
+    let mut bricks: Vec<_> = data
+        .bricks
+        .iter()
+        .map(|brick| (brick_bounds(brick), brick))
+        .collect();
+
+    if bricks.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "save has no bricks",
+        ));
+    }
+
+    let mut min = [i64::MAX; 2];
+    let mut max = [i64::MIN; 2];
+    for ((lo, hi), _) in &bricks {
+        for axis in 0..2 {
+            min[axis] = min[axis].min(lo[axis]);
+            max[axis] = max[axis].max(hi[axis]);
+        }
+    }
+
+    let extent = (max[0] - min[0]).max(max[1] - min[1]).max(1);
+    let scale = f64::from(max_size.max(1)) / extent as f64;
+    let width = (((max[0] - min[0]) as f64 * scale).ceil() as u32).max(1);
+    let height = (((max[1] - min[1]) as f64 * scale).ceil() as u32).max(1);
+    let to_pixel =
+        |u: i64, axis: usize, size: u32| (((u - min[axis]) as f64 * scale) as u32).min(size - 1);
+
+    // Draw bottom to top so the highest brick at a spot is the one visible
+    bricks.sort_by_key(|((_, hi), _)| hi[2]);
+
+    let mut pixels = vec![0u8; width as usize * height as usize * 3];
+
+    for ((lo, hi), brick) in bricks {
+        let color = resolve_color(data, &brick.color);
+        let rgb = [
+            linear_to_srgb(color.r()),
+            linear_to_srgb(color.g()),
+            linear_to_srgb(color.b()),
+        ];
+
+        let (x0, x1) = (to_pixel(lo[0], 0, width), to_pixel(hi[0], 0, width));
+        let (y0, y1) = (to_pixel(lo[1], 1, height), to_pixel(hi[1], 1, height));
+
+        for y in y0..=y1 {
+            let row = y as usize * width as usize;
+            for x in x0..=x1 {
+                let i = (row + x as usize) * 3;
+                pixels[i..i + 3].copy_from_slice(&rgb);
+            }
+        }
+    }
+
+    let mut encoder = png::Encoder::new(w, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&pixels).map_err(png_error)?;
+
+    Ok(())
+}
+
+/// Writes a PNG strip comparing a save's palette to the converted colors, `swatch` pixels
+/// wide per color. The top half of each swatch is the Blockland color, and the bottom half
+/// the converted color as Brickadia shows it. Alpha is ignored.
+pub fn write_palette_png(
+    w: impl Write,
+    source: &[(f32, f32, f32, f32)],
+    converted: &[brs::Color],
+    swatch: u32,
+) -> io::Result<()> {
+    let count = source.len().min(converted.len());
+    if count == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "palette has no colors",
+        ));
+    }
+
+    let swatch = swatch.max(1) as usize;
+    let (width, height) = (count * swatch, swatch * 2);
+    let mut pixels = vec![0u8; width * height * 3];
+
+    let to_byte = |u: f32| (u * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    for (index, (&(r, g, b, _), color)) in source.iter().zip(converted).enumerate() {
+        let halves = [
+            [to_byte(r), to_byte(g), to_byte(b)],
+            [
+                linear_to_srgb(color.r()),
+                linear_to_srgb(color.g()),
+                linear_to_srgb(color.b()),
+            ],
+        ];
+
+        for y in 0..height {
+            let rgb = halves[y / swatch];
+            let row = y * width;
+            for x in index * swatch..(index + 1) * swatch {
+                let i = (row + x) * 3;
+                pixels[i..i + 3].copy_from_slice(&rgb);
+            }
+        }
+    }
+
+    let mut encoder = png::Encoder::new(w, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&pixels).map_err(png_error)?;
+
+    Ok(())
+}
+
+/// Inverse of the color conversion done when converting saves.
+fn linear_to_srgb(u: u8) -> u8 {
+    let u = f32::from(u) / 255.0;
+    let u = if u <= 0.003_130_8 {
+        u * 12.92
+    } else {
+        1.055 * u.powf(1.0 / 2.4) - 0.055
+    };
+    (u * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn png_error(e: png::EncodingError) -> io::Error {
+    io::Error::other(e)
+}
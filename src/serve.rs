@@ -0,0 +1,328 @@
+//! A small HTTP API for converting saves, for websites and bots that would otherwise run
+//! bls2brs once per save.
+//!
+//! - `POST /convert` with a bls file as the body, which may be gzipped, answers with a
+//!   JSON object holding the brs file in base64 as `brs` and `ConvertReport::to_json` as
+//!   `report`.
+//! - `GET /health` answers `ok`, for load balancers.
+//!
+//! Errors are answered with a JSON object holding an `error` message.
+//!
+//! At most `MAX_CONNECTIONS` requests are handled at once, and each conversion is held to
+//! a memory limit, so one client can't take the server down.
+
+use crate::{
+    archive::{self, Compression},
+    convert_io, ConvertOptions,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use std::{
+    io::{self, prelude::*, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{Condvar, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// Largest request body accepted, in bytes. Also limits how large a gzipped save may get
+/// once decompressed.
+pub const MAX_UPLOAD: u64 = 256 * 1024 * 1024;
+/// Most connections handled at once. Further connections wait to be accepted.
+pub const MAX_CONNECTIONS: usize = 8;
+/// Memory limit for each conversion, unless `ConvertOptions::memory_limit` is set.
+pub const DEFAULT_MEMORY_LIMIT: u64 = 1024 * 1024 * 1024;
+/// How long reading or writing may stall before the connection is dropped.
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest request or header line accepted, in bytes.
+const MAX_LINE: u64 = 8 * 1024;
+/// Most header lines accepted.
+const MAX_HEADERS: usize = 100;
+
+struct Response {
+    status: u16,
+    reason: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(status: u16, reason: &'static str, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            reason,
+            content_type: "application/json",
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    fn error(status: u16, reason: &'static str, message: impl Into<String>) -> Self {
+        Self::json(
+            status,
+            reason,
+            &serde_json::json!({ "error": message.into() }),
+        )
+    }
+}
+
+/// Counts the connections being handled, to wait for one to finish before accepting
+/// more than `MAX_CONNECTIONS`.
+struct Slots {
+    used: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl Slots {
+    fn acquire(&self) -> Slot<'_> {
+        let mut used = self.used.lock().unwrap();
+        while *used >= MAX_CONNECTIONS {
+            used = self.freed.wait(used).unwrap();
+        }
+        *used += 1;
+        Slot(self)
+    }
+}
+
+/// A connection being handled, freed when dropped.
+struct Slot<'a>(&'a Slots);
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        *self.0.used.lock().unwrap() -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+/// Answers requests on `listener` with saves converted using `options`, up to
+/// `MAX_CONNECTIONS` at once, each on its own thread. Converted saves with more than
+/// `max_bricks` bricks are refused. Runs until the process exits.
+pub fn serve(listener: TcpListener, mut options: ConvertOptions, max_bricks: Option<usize>) {
+    if options.memory_limit.is_none() {
+        options.memory_limit = Some(DEFAULT_MEMORY_LIMIT);
+    }
+    let options = &options;
+    let slots = Slots {
+        used: Mutex::new(0),
+        freed: Condvar::new(),
+    };
+
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Failed to accept a connection: {}", e);
+                    continue;
+                }
+            };
+
+            let slot = slots.acquire();
+            scope.spawn(move || {
+                let _slot = slot;
+                if let Err(e) = handle(stream, options, max_bricks) {
+                    log::debug!("Connection failed: {}", e);
+                }
+            });
+        }
+    });
+}
+
+fn handle(
+    stream: TcpStream,
+    options: &ConvertOptions,
+    max_bricks: Option<usize>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let peer = stream.peer_addr()?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = respond(&mut reader, options, max_bricks)?;
+    log::info!("{}: {} {}", peer, response.status, response.reason);
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// Reads one request and answers it. Fails only if the connection does.
+fn respond(
+    reader: &mut impl BufRead,
+    options: &ConvertOptions,
+    max_bricks: Option<usize>,
+) -> io::Result<Response> {
+    let request_line = match read_line(reader)? {
+        Some(line) => line,
+        None => {
+            return Ok(Response::error(
+                414,
+                "URI Too Long",
+                "request line too long",
+            ))
+        }
+    };
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Ok(Response::error(400, "Bad Request", "invalid request line")),
+    };
+
+    let mut content_length = None;
+    let mut headers_ended = false;
+    for _ in 0..MAX_HEADERS {
+        let line = match read_line(reader)? {
+            Some(line) => line,
+            None => break,
+        };
+        if line.is_empty() {
+            headers_ended = true;
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<u64>().ok();
+            }
+        }
+    }
+    if !headers_ended {
+        return Ok(Response::error(
+            431,
+            "Request Header Fields Too Large",
+            "too many or too long headers",
+        ));
+    }
+
+    let path = target.split('?').next().unwrap_or_default();
+    match (method.as_str(), path) {
+        ("GET", "/health") => {
+            return Ok(Response {
+                status: 200,
+                reason: "OK",
+                content_type: "text/plain",
+                body: b"ok".to_vec(),
+            })
+        }
+        ("POST", "/convert") => {}
+        (_, "/health") | (_, "/convert") => {
+            return Ok(Response::error(
+                405,
+                "Method Not Allowed",
+                format!("{} is not allowed on {}", method, path),
+            ))
+        }
+        _ => return Ok(Response::error(404, "Not Found", "no such endpoint")),
+    }
+
+    let length = match content_length {
+        Some(length) => length,
+        None => {
+            return Ok(Response::error(
+                411,
+                "Length Required",
+                "send the save with a Content-Length",
+            ))
+        }
+    };
+    if length > MAX_UPLOAD {
+        return Ok(Response::error(
+            413,
+            "Payload Too Large",
+            format!("saves can be at most {} bytes", MAX_UPLOAD),
+        ));
+    }
+
+    let mut body = Vec::new();
+    reader.take(length).read_to_end(&mut body)?;
+    if body.len() as u64 != length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(convert(&body, options, max_bricks))
+}
+
+/// Reads a line without its line ending. `None` if it is longer than `MAX_LINE`.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    reader.take(MAX_LINE).read_until(b'\n', &mut line)?;
+    if !line.ends_with(b"\n") {
+        if line.len() as u64 == MAX_LINE {
+            return Ok(None);
+        }
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(Some(
+        String::from_utf8_lossy(&line)
+            .trim_end_matches(['\r', '\n'])
+            .to_string(),
+    ))
+}
+
+fn convert(body: &[u8], options: &ConvertOptions, max_bricks: Option<usize>) -> Response {
+    let mut brs = Vec::new();
+    let result = match archive::detect(&mut &body[..]) {
+        Ok(Compression::Zip) => {
+            return Response::error(
+                415,
+                "Unsupported Media Type",
+                "zip archives can't be converted, send each save on its own",
+            )
+        }
+        Ok(Compression::Gzip) => {
+            // One more byte than allowed, to tell a save of exactly `MAX_UPLOAD` from a
+            // larger one
+            let mut bls = Vec::new();
+            let read = archive::gunzip(body)
+                .take(MAX_UPLOAD + 1)
+                .read_to_end(&mut bls);
+            match read {
+                Ok(_) if bls.len() as u64 > MAX_UPLOAD => {
+                    return Response::error(
+                        413,
+                        "Payload Too Large",
+                        format!("saves can be at most {} bytes decompressed", MAX_UPLOAD),
+                    )
+                }
+                Ok(_) => convert_io(&bls[..], &mut brs, options),
+                Err(e) => Err(e),
+            }
+        }
+        Ok(Compression::None) | Err(_) => convert_io(body, &mut brs, options),
+    };
+
+    if let (Ok(report), Some(max)) = (&result, max_bricks) {
+        let bricks = report.write_data.bricks.len();
+        if bricks > max {
+            return Response::error(
+                413,
+                "Payload Too Large",
+                format!(
+                    "the converted save would have {} bricks, more than the limit of {}",
+                    bricks, max
+                ),
+            );
+        }
+    }
+
+    match result {
+        Ok(report) => Response::json(
+            200,
+            "OK",
+            &serde_json::json!({
+                "brs": BASE64.encode(&brs),
+                "report": report.to_json(),
+            }),
+        ),
+        Err(e) => Response::error(
+            422,
+            "Unprocessable Entity",
+            format!("failed to convert bls file: {}", e),
+        ),
+    }
+}
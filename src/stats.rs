@@ -0,0 +1,56 @@
+//! An inventory of a bls file, for cataloging saves without converting them.
+
+use crate::extra;
+use std::{
+    collections::HashMap,
+    io::{self, prelude::*},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct SaveStats {
+    pub brick_count: usize,
+    /// Bricks of each ui name.
+    pub ui_names: HashMap<String, usize>,
+    /// The save's palette, as RGBA from 0 to 1.
+    pub colors: Vec<(f32, f32, f32, f32)>,
+    /// Bricks of each palette index.
+    pub color_counts: Vec<usize>,
+    /// Lowest and highest brick position, in Blockland units.
+    pub bounds: Option<([f32; 3], [f32; 3])>,
+    /// Bricks planted by each BL_ID. `None` for bricks without an owner.
+    pub owners: HashMap<Option<u32>, usize>,
+}
+
+/// Counts the bricks in a bls file by name, color and owner.
+pub fn stats(reader: bl_save::Reader<impl BufRead>) -> io::Result<SaveStats> {
+    let colors = reader.colors().to_vec();
+    let mut stats = SaveStats {
+        color_counts: vec![0; colors.len()],
+        colors,
+        ..SaveStats::default()
+    };
+
+    for from in reader {
+        let from = from?;
+        let (x, y, z) = from.base.position;
+        let position = [x, y, z];
+
+        stats.brick_count += 1;
+        *stats.ui_names.entry(from.base.ui_name.clone()).or_default() += 1;
+        if let Some(count) = stats
+            .color_counts
+            .get_mut(usize::from(from.base.color_index))
+        {
+            *count += 1;
+        }
+        *stats.owners.entry(extra::owner_id(&from)).or_default() += 1;
+
+        let (min, max) = stats.bounds.get_or_insert((position, position));
+        for axis in 0..3 {
+            min[axis] = min[axis].min(position[axis]);
+            max[axis] = max[axis].max(position[axis]);
+        }
+    }
+
+    Ok(stats)
+}
@@ -1,17 +1,43 @@
+use serde::{Deserialize, Serialize};
+
 pub type BrickMapping = Vec<BrickDesc>;
 
+/// The face of a brick that a print is on, relative to the brick's rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrintFace {
+    Top,
+    /// The side facing the brick's forward direction, as on `1x1 Print`.
+    Front,
+    /// The sloped face of a ramp.
+    Slope,
+}
+
 #[derive(Debug, Clone)]
 pub struct BrickDesc {
     pub asset: &'static str,
     pub size: (u32, u32, u32),
     pub offset: (i32, i32, i32),
     pub rotation_offset: u8,
-    pub color_override: Option<brs::Color>,
+    /// RGBA, added to the palette if needed.
+    pub color_override: Option<[u8; 4]>,
     pub direction_override: Option<brs::Direction>,
     pub non_priority: bool,
+    /// Used instead of the source brick's collision, for cosmetic parts.
+    pub collision_override: Option<bool>,
+    /// Used instead of the source brick's rendering.
+    pub visibility_override: Option<bool>,
     pub microwedge_rotate: bool,
     pub inverted_modter_rotate: bool,
     pub inverted_wedge_rotate: bool,
+    /// The source brick's print can't be shown on this asset.
+    pub print_dropped: bool,
+    /// Where the source brick's print goes on this brick, see `ConvertReport::prints`.
+    pub print_face: Option<PrintFace>,
+    /// Only resembles the Blockland brick, see `ConvertReport::approximate_ui_names`.
+    pub approximate: bool,
+    /// Shows the source brick's print on a face of another part. Only written if
+    /// `ConvertOptions::print_assets` has the print, as that asset.
+    pub decal: bool,
 }
 
 impl BrickDesc {
@@ -24,60 +50,90 @@ impl BrickDesc {
             color_override: None,
             direction_override: None,
             non_priority: false,
+            collision_override: None,
+            visibility_override: None,
             microwedge_rotate: false,
             inverted_modter_rotate: false,
             inverted_wedge_rotate: false,
+            print_dropped: false,
+            print_face: None,
+            approximate: false,
+            decal: false,
         }
     }
 
-    pub fn size(mut self, size: (u32, u32, u32)) -> Self {
+    pub const fn size(mut self, size: (u32, u32, u32)) -> Self {
         self.size = size;
         self
     }
 
-    pub fn offset(mut self, offset: (i32, i32, i32)) -> Self {
+    pub const fn offset(mut self, offset: (i32, i32, i32)) -> Self {
         self.offset = offset;
         self
     }
 
-    pub fn rotation_offset(mut self, rotation: u8) -> Self {
+    pub const fn rotation_offset(mut self, rotation: u8) -> Self {
         self.rotation_offset = rotation;
         self
     }
 
-    pub fn color_override(mut self, color_override: brs::Color) -> Self {
+    pub const fn color_override(mut self, color_override: [u8; 4]) -> Self {
         self.color_override = Some(color_override);
         self
     }
 
-    pub fn direction_override(mut self, direction_override: brs::Direction) -> Self {
+    pub const fn direction_override(mut self, direction_override: brs::Direction) -> Self {
         self.direction_override = Some(direction_override);
         self
     }
 
-    pub fn non_priority(mut self, non_priority: bool) -> Self {
+    pub const fn non_priority(mut self, non_priority: bool) -> Self {
         self.non_priority = non_priority;
         self
     }
 
-    pub fn microwedge_rotate(mut self, microwedge_rotate: bool) -> Self {
+    pub const fn collision_override(mut self, collision: bool) -> Self {
+        self.collision_override = Some(collision);
+        self
+    }
+
+    pub const fn visibility_override(mut self, visibility: bool) -> Self {
+        self.visibility_override = Some(visibility);
+        self
+    }
+
+    pub const fn microwedge_rotate(mut self, microwedge_rotate: bool) -> Self {
         self.microwedge_rotate = microwedge_rotate;
         self
     }
 
-    pub fn inverted_modter_rotate(mut self, inverted_modter_rotate: bool) -> Self {
+    pub const fn inverted_modter_rotate(mut self, inverted_modter_rotate: bool) -> Self {
         self.inverted_modter_rotate = inverted_modter_rotate;
         self
     }
 
-    pub fn inverted_wedge_rotate(mut self, inverted_wedge_rotate: bool) -> Self {
+    pub const fn inverted_wedge_rotate(mut self, inverted_wedge_rotate: bool) -> Self {
         self.inverted_wedge_rotate = inverted_wedge_rotate;
         self
     }
-}
 
-impl From<BrickDesc> for BrickMapping {
-    fn from(desc: BrickDesc) -> Self {
-        vec![desc]
+    pub const fn print_dropped(mut self, print_dropped: bool) -> Self {
+        self.print_dropped = print_dropped;
+        self
+    }
+
+    pub const fn print_face(mut self, print_face: PrintFace) -> Self {
+        self.print_face = Some(print_face);
+        self
+    }
+
+    pub const fn approximate(mut self, approximate: bool) -> Self {
+        self.approximate = approximate;
+        self
+    }
+
+    pub const fn decal(mut self, decal: bool) -> Self {
+        self.decal = decal;
+        self
     }
 }
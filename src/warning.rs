@@ -0,0 +1,242 @@
+//! What was lost or changed converting a save, see `ConvertReport::warnings`.
+
+use crate::{ConvertOptions, ConvertReport, OutOfRangePolicy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Palette colors Brickadia's color picker has room for. Bricks of colors past it keep
+/// their color but can't be painted with it again.
+pub const PALETTE_LIMIT: usize = 256;
+
+/// How much a warning matters, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// The build is complete, but something is different from Blockland.
+    Info,
+    /// Something is missing that can be redone in game.
+    Warning,
+    /// Bricks are missing or won't load.
+    Error,
+}
+
+/// Something lost or changed converting a save. Displays as one short sentence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConvertWarning {
+    /// Source bricks that failed to convert, `unknown` of them with one of `unknown_names`.
+    FailedBricks {
+        count: usize,
+        unknown: usize,
+        unknown_names: usize,
+    },
+    /// Brick lines that couldn't be read, see `ConvertOptions::lenient`.
+    UnreadableBricks { count: usize },
+    /// Source bricks converted to something that only resembles them, with their ui names
+    /// sorted.
+    ApproximateMapping { count: usize, ui_names: Vec<String> },
+    /// Interactive bricks replaced by `ConvertOptions::placeholders`.
+    Placeholders { count: usize },
+    /// Bricks whose print the output asset can't show.
+    DroppedPrints { count: usize },
+    /// Bricks with events, which brs files can't hold.
+    DroppedEvents { count: usize },
+    /// Teledoors and teleporters written as portal placeholders, see
+    /// `ConvertReport::portals`.
+    Portals { count: usize },
+    /// Checkpoints, capture points and spawns written as markers, see
+    /// `ConvertReport::minigame_bricks`.
+    MinigameBricks { count: usize },
+    /// Bricks that played music, which brs files can't hold.
+    DroppedMusic { count: usize },
+    /// Bricks with the undulo shape effect.
+    LostUndulo { count: usize },
+    /// Bricks with an animated color effect, see `ConvertOptions::animated_fx`.
+    LostColorEffects { count: usize },
+    /// The palette has more colors than `PALETTE_LIMIT`.
+    PaletteOverflow { colors: usize },
+    /// Output bricks moved into range by `OutOfRangePolicy::Clamp`.
+    ClampedPosition { count: usize },
+    /// Output bricks left beyond `ConvertOptions::coordinate_limit` by
+    /// `OutOfRangePolicy::Keep`.
+    OutOfRange { count: usize },
+    /// Assets that aren't in `ConvertOptions::known_assets`, so their bricks load
+    /// invisible.
+    UnknownAssets { assets: Vec<String> },
+}
+
+impl ConvertWarning {
+    pub fn severity(&self) -> Severity {
+        use ConvertWarning::*;
+
+        match self {
+            ApproximateMapping { .. } | Placeholders { .. } | LostUndulo { .. } => Severity::Info,
+            DroppedPrints { .. }
+            | DroppedEvents { .. }
+            | Portals { .. }
+            | MinigameBricks { .. }
+            | DroppedMusic { .. }
+            | LostColorEffects { .. }
+            | PaletteOverflow { .. }
+            | ClampedPosition { .. } => Severity::Warning,
+            FailedBricks { .. }
+            | UnreadableBricks { .. }
+            | OutOfRange { .. }
+            | UnknownAssets { .. } => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for ConvertWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConvertWarning::*;
+
+        match self {
+            FailedBricks {
+                count,
+                unknown,
+                unknown_names,
+            } => write!(
+                f,
+                "{} bricks failed to convert, {} of them with {} unknown names",
+                count, unknown, unknown_names
+            ),
+            UnreadableBricks { count } => write!(f, "{} bricks could not be read", count),
+            ApproximateMapping { count, .. } => write!(f, "{} bricks were approximated", count),
+            Placeholders { count } => write!(
+                f,
+                "{} interactive bricks were replaced with placeholders",
+                count
+            ),
+            DroppedPrints { count } => write!(f, "{} bricks lost their prints", count),
+            DroppedEvents { count } => write!(f, "{} bricks lost their events", count),
+            Portals { count } => write!(
+                f,
+                "{} teleporters were written as portals without their links",
+                count
+            ),
+            MinigameBricks { count } => write!(
+                f,
+                "{} minigame bricks were written as markers without their logic",
+                count
+            ),
+            DroppedMusic { count } => write!(f, "{} music bricks lost their music", count),
+            LostUndulo { count } => write!(f, "{} bricks lost their undulo waves", count),
+            LostColorEffects { count } => {
+                write!(f, "{} bricks lost their animated color effects", count)
+            }
+            PaletteOverflow { colors } => write!(
+                f,
+                "the palette has {} colors, more than the {} Brickadia's color picker has room for",
+                colors, PALETTE_LIMIT
+            ),
+            ClampedPosition { count } => write!(
+                f,
+                "{} bricks were moved into Brickadia's coordinate range",
+                count
+            ),
+            OutOfRange { count } => write!(
+                f,
+                "{} bricks are outside of Brickadia's coordinate range",
+                count
+            ),
+            UnknownAssets { assets } => write!(
+                f,
+                "bricks use assets Brickadia doesn't have: {}",
+                assets.join(", ")
+            ),
+        }
+    }
+}
+
+/// The warnings for a finished report, most severe first.
+pub(crate) fn collect(report: &ConvertReport, options: &ConvertOptions) -> Vec<ConvertWarning> {
+    use ConvertWarning::*;
+
+    let mut warnings = Vec::new();
+
+    if report.count_failure > 0 {
+        warnings.push(FailedBricks {
+            count: report.count_failure,
+            unknown: report.unknown_ui_names.values().sum(),
+            unknown_names: report.unknown_ui_names.len(),
+        });
+    }
+    if report.count_parse_errors > 0 {
+        warnings.push(UnreadableBricks {
+            count: report.count_parse_errors,
+        });
+    }
+    if !report.approximate_ui_names.is_empty() {
+        let mut ui_names: Vec<_> = report.approximate_ui_names.keys().cloned().collect();
+        ui_names.sort();
+        warnings.push(ApproximateMapping {
+            count: report.approximate_ui_names.values().sum(),
+            ui_names,
+        });
+    }
+    if !report.placeholders.is_empty() {
+        warnings.push(Placeholders {
+            count: report.placeholders.len(),
+        });
+    }
+    if !report.dropped_prints.is_empty() {
+        warnings.push(DroppedPrints {
+            count: report.dropped_prints.len(),
+        });
+    }
+    if report.count_dropped_events > 0 {
+        warnings.push(DroppedEvents {
+            count: report.count_dropped_events,
+        });
+    }
+    if !report.portals.is_empty() {
+        warnings.push(Portals {
+            count: report.portals.len(),
+        });
+    }
+    if !report.minigame_bricks.is_empty() {
+        warnings.push(MinigameBricks {
+            count: report.minigame_bricks.len(),
+        });
+    }
+    if report.count_undulo > 0 {
+        warnings.push(LostUndulo {
+            count: report.count_undulo,
+        });
+    }
+    if !report.animated_fx.is_empty() {
+        warnings.push(LostColorEffects {
+            count: report.animated_fx.values().sum(),
+        });
+    }
+    if !report.music_bricks.is_empty() {
+        warnings.push(DroppedMusic {
+            count: report.music_bricks.len(),
+        });
+    }
+    if report.write_data.colors.len() > PALETTE_LIMIT {
+        warnings.push(PaletteOverflow {
+            colors: report.write_data.colors.len(),
+        });
+    }
+    if !report.unknown_assets.is_empty() {
+        warnings.push(UnknownAssets {
+            assets: report.unknown_assets.clone(),
+        });
+    }
+    if report.count_out_of_range > 0 {
+        match options.out_of_range {
+            OutOfRangePolicy::Keep => warnings.push(OutOfRange {
+                count: report.count_out_of_range,
+            }),
+            OutOfRangePolicy::Clamp => warnings.push(ClampedPosition {
+                count: report.count_out_of_range,
+            }),
+            // Counted as failures
+            OutOfRangePolicy::Drop => {}
+        }
+    }
+
+    // Stable, so warnings of the same severity keep their order
+    warnings.sort_by_key(|warning| std::cmp::Reverse(warning.severity()));
+    warnings
+}
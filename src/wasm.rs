@@ -0,0 +1,50 @@
+//! Browser-facing API, built with `--features wasm`.
+
+use crate::{convert_io, ConvertOptions};
+use std::io::BufReader;
+use wasm_bindgen::prelude::*;
+
+/// A converted save together with its report.
+#[wasm_bindgen]
+pub struct Conversion {
+    brs: Vec<u8>,
+    report: String,
+}
+
+#[wasm_bindgen]
+impl Conversion {
+    /// The `.brs` file contents.
+    #[wasm_bindgen(getter)]
+    pub fn brs(&self) -> Vec<u8> {
+        self.brs.clone()
+    }
+
+    /// The conversion report as JSON.
+    #[wasm_bindgen(getter)]
+    pub fn report(&self) -> String {
+        self.report.clone()
+    }
+}
+
+/// Converts the contents of a `.bls` file to the contents of a `.brs` file.
+#[wasm_bindgen]
+pub fn convert_bytes(bls: &[u8]) -> Result<Vec<u8>, JsValue> {
+    Ok(convert_bytes_with_report(bls)?.brs)
+}
+
+/// Like `convert_bytes`, but also returns the report.
+#[wasm_bindgen]
+pub fn convert_bytes_with_report(bls: &[u8]) -> Result<Conversion, JsValue> {
+    let mut brs = Vec::new();
+    let converted =
+        convert_io(BufReader::new(bls), &mut brs, &ConvertOptions::default()).map_err(js_error)?;
+
+    Ok(Conversion {
+        brs,
+        report: converted.to_json().to_string(),
+    })
+}
+
+fn js_error(e: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
@@ -0,0 +1,46 @@
+//! Builds and converts small saves for the tests that check single bricks.
+
+// Each test file uses only some of these
+#![allow(dead_code)]
+
+use bls2brs::{bl_save, ConvertOptions, ConvertReport};
+use std::io::Cursor;
+
+/// A save with an all-white colorset holding `bricks`, each a brick line of the bls format.
+pub fn save(bricks: &[&str]) -> String {
+    let mut bls = String::from("This is a Blockland save file.\r\n1\r\nTest save\r\n");
+    for _ in 0..64 {
+        bls.push_str("1.000000 1.000000 1.000000 1.000000\r\n");
+    }
+    bls.push_str(&format!("Linecount {}\r\n", bricks.len()));
+    for brick in bricks {
+        bls.push_str(brick);
+        bls.push_str("\r\n");
+    }
+    bls
+}
+
+/// Converts a save holding `bricks` with `options`.
+pub fn convert(bricks: &[&str], options: &ConvertOptions) -> ConvertReport {
+    let reader = bl_save::Reader::new(Cursor::new(save(bricks))).unwrap();
+    bls2brs::convert_with_options(reader, options).unwrap()
+}
+
+/// Converts a single `ui_name` brick at the origin with `options`.
+pub fn convert_brick(ui_name: &str, options: &ConvertOptions) -> ConvertReport {
+    convert(
+        &[&format!("{}\" 0 0 0.3 0 0 0  0 0 1 1 1", ui_name)],
+        options,
+    )
+}
+
+/// The asset and size of each brick written.
+pub fn bricks(report: &ConvertReport) -> Vec<(String, (u32, u32, u32))> {
+    let assets = &report.write_data.brick_assets;
+    report
+        .write_data
+        .bricks
+        .iter()
+        .map(|brick| (assets[brick.asset_name_index as usize].clone(), brick.size))
+        .collect()
+}
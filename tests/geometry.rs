@@ -0,0 +1,74 @@
+use bls2brs::{
+    brs::Direction::*,
+    geometry::{brickadia_position, is_horizontal, rotate_90_2d, rotate_direction, rotate_offset},
+};
+
+#[test]
+fn rotate_offset_all_angles() {
+    assert_eq!(rotate_offset((3, 1), 0), (3, 1));
+    assert_eq!(rotate_offset((3, 1), 1), (-1, 3));
+    assert_eq!(rotate_offset((3, 1), 2), (-3, -1));
+    assert_eq!(rotate_offset((3, 1), 3), (1, -3));
+}
+
+#[test]
+fn rotate_offset_wraps() {
+    for angle in 0..4 {
+        assert_eq!(
+            rotate_offset((3, 1), angle + 4),
+            rotate_offset((3, 1), angle)
+        );
+    }
+}
+
+#[test]
+fn rotate_90_2d_matches_rotate_offset() {
+    assert_eq!(rotate_90_2d((3, 1)), rotate_offset((3, 1), 1));
+    assert_eq!(rotate_90_2d((-2.5f32, 4.0f32)), (-4.0, -2.5));
+}
+
+#[test]
+fn rotate_direction_all_angles() {
+    assert_eq!(rotate_direction(XPositive, 0), XPositive);
+    assert_eq!(rotate_direction(XPositive, 1), YPositive);
+    assert_eq!(rotate_direction(XPositive, 2), XNegative);
+    assert_eq!(rotate_direction(XPositive, 3), YNegative);
+    assert_eq!(rotate_direction(YNegative, 1), XPositive);
+}
+
+#[test]
+fn rotate_direction_keeps_vertical() {
+    for angle in 0..4 {
+        assert_eq!(rotate_direction(ZPositive, angle), ZPositive);
+        assert_eq!(rotate_direction(ZNegative, angle), ZNegative);
+    }
+    assert!(!is_horizontal(ZPositive));
+    assert!(is_horizontal(YNegative));
+}
+
+#[test]
+fn rotate_direction_follows_offsets() {
+    // An offset along a direction stays along it after both are turned
+    let offsets = [
+        (XPositive, (1, 0)),
+        (YPositive, (0, 1)),
+        (XNegative, (-1, 0)),
+        (YNegative, (0, -1)),
+    ];
+    for angle in 0..4 {
+        for (direction, offset) in offsets {
+            let turned = rotate_offset(offset, angle);
+            let expected = offsets.iter().find(|(_, o)| *o == turned).unwrap().0;
+            assert_eq!(rotate_direction(direction, angle), expected);
+        }
+    }
+}
+
+#[test]
+fn brickadia_position_swaps_axes() {
+    // Blockland X is Brickadia Y, and a Blockland unit is two studs of 10 units
+    assert_eq!(brickadia_position((1.0, 0.0, 0.0)), (0, 20, 0));
+    assert_eq!(brickadia_position((0.0, 1.0, 0.0)), (20, 0, 0));
+    assert_eq!(brickadia_position((0.0, 0.0, 0.2)), (0, 0, 4));
+    assert_eq!(brickadia_position((-0.5, 2.25, 0.1)), (45, -10, 2));
+}
@@ -0,0 +1,125 @@
+//! Converts each `tests/golden/*.bls` fixture with the default options and compares the
+//! bricks against the `.json` file next to it.
+//!
+//! After changing `src/mappings.rs` or adding a fixture, run
+//! `BLS2BRS_BLESS=1 cargo test --test golden` to rewrite the expected files, and check
+//! the diff.
+
+use bls2brs::{bl_save, json::SaveJson, ConvertOptions};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+const BLESS_VAR: &str = "BLS2BRS_BLESS";
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden");
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bls"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn snapshot(path: &Path) -> Value {
+    let reader = bl_save::Reader::new(BufReader::new(File::open(path).unwrap())).unwrap();
+    let report = bls2brs::convert_with_options(reader, &ConvertOptions::default()).unwrap();
+    let save = SaveJson::from_write_data(&report.write_data);
+    let unknown_ui_names: BTreeMap<_, _> = report.unknown_ui_names.into_iter().collect();
+
+    json!({
+        "brick_assets": save.brick_assets,
+        "bricks": save.bricks,
+        "unknown_ui_names": unknown_ui_names,
+    })
+}
+
+/// Describes how `actual` differs from `expected`, brick by brick where possible.
+fn diff(expected: &Value, actual: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for key in ["brick_assets", "unknown_ui_names"] {
+        if expected[key] != actual[key] {
+            lines.push(format!(
+                "{}: expected {}, got {}",
+                key, expected[key], actual[key]
+            ));
+        }
+    }
+
+    let empty = Vec::new();
+    let expected_bricks = expected["bricks"].as_array().unwrap_or(&empty);
+    let actual_bricks = actual["bricks"].as_array().unwrap_or(&empty);
+
+    if expected_bricks.len() != actual_bricks.len() {
+        lines.push(format!(
+            "expected {} bricks, got {}",
+            expected_bricks.len(),
+            actual_bricks.len()
+        ));
+    }
+
+    for (index, (expected, actual)) in expected_bricks.iter().zip(actual_bricks).enumerate() {
+        if expected != actual {
+            lines.push(format!(
+                "brick {}: expected {}, got {}",
+                index, expected, actual
+            ));
+        }
+    }
+
+    lines
+}
+
+#[test]
+fn golden() {
+    let bless = env::var_os(BLESS_VAR).is_some();
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in tests/golden");
+
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let actual = snapshot(fixture);
+        let golden_path = fixture.with_extension("json");
+
+        if bless {
+            let text = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+            fs::write(&golden_path, text).unwrap();
+            continue;
+        }
+
+        let expected: Value = match fs::read_to_string(&golden_path) {
+            Ok(text) => serde_json::from_str(&text).unwrap(),
+            Err(_) => {
+                failures.push(format!(
+                    "{}: no expected output, run with {}=1 to create it",
+                    fixture.display(),
+                    BLESS_VAR
+                ));
+                continue;
+            }
+        };
+
+        let lines = diff(&expected, &actual);
+        if !lines.is_empty() {
+            failures.push(format!("{}:\n  {}", fixture.display(), lines.join("\n  ")));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "conversion changed, run with {}=1 to accept it:\n{}",
+        BLESS_VAR,
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,12 @@
+//! Runs `bls2brs lint` over the mapping tables, so a bad mapping fails the build.
+
+use bls2brs::lint::lint_mappings;
+
+#[test]
+fn mappings_are_clean() {
+    let issues: Vec<_> = lint_mappings()
+        .into_iter()
+        .map(|issue| format!("{} #{}: {}", issue.ui_name, issue.desc_index, issue.message))
+        .collect();
+    assert!(issues.is_empty(), "{}", issues.join("\n"));
+}
@@ -0,0 +1,37 @@
+//! Converts a small save with `ConvertOptions::owner` set and checks who owns the bricks.
+
+mod common;
+
+use bls2brs::{
+    brs::{uuid::Uuid, User},
+    ConvertOptions,
+};
+
+#[test]
+fn owner_owns_every_brick() {
+    let user = User {
+        id: Uuid::from_bytes([7; 16]),
+        name: String::from("Builder"),
+    };
+    let options = ConvertOptions {
+        owner: Some(user.clone()),
+        ..ConvertOptions::default()
+    };
+    let report = common::convert(
+        &[
+            "2x2\" 0 0 0.3 0 0 0  0 0 1 1 1",
+            "1x1\" 2 0 0.3 0 0 0  0 0 1 1 1",
+        ],
+        &options,
+    );
+    let data = &report.write_data;
+
+    assert_eq!(data.bricks.len(), 2);
+    for brick in &data.bricks {
+        // Owner indices are 1-based into `brick_owners`
+        let owner = &data.brick_owners[brick.owner_index as usize - 1];
+        assert_eq!(owner.id, user.id);
+        assert_eq!(owner.name, user.name);
+    }
+    assert_eq!(report.owner_brick_counts, vec![0, 2]);
+}
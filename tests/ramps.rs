@@ -0,0 +1,86 @@
+//! Converts single ramp bricks, one per naming scheme, and checks the bricks written.
+
+mod common;
+
+use bls2brs::ConvertOptions;
+
+/// The asset and size of each brick that `ui_name` converts to.
+fn convert(ui_name: &str) -> Vec<(String, (u32, u32, u32))> {
+    common::bricks(&common::convert_brick(ui_name, &ConvertOptions::default()))
+}
+
+fn ramp(asset: &str, size: (u32, u32, u32)) -> Vec<(String, (u32, u32, u32))> {
+    vec![(asset.to_string(), size)]
+}
+
+#[test]
+fn angled_ramps() {
+    assert_eq!(convert("45° Ramp"), ramp("PB_DefaultRamp", (10, 10, 6)));
+    assert_eq!(convert("25° Ramp"), ramp("PB_DefaultRamp", (15, 15, 6)));
+    assert_eq!(
+        convert("-65° Ramp"),
+        ramp("PB_DefaultRampInverted", (10, 10, 12))
+    );
+}
+
+#[test]
+fn wide_ramps() {
+    assert_eq!(convert("45° Ramp 6x"), ramp("PB_DefaultRamp", (10, 30, 6)));
+    assert_eq!(convert("72° Ramp 4x"), ramp("PB_DefaultRamp", (10, 20, 18)));
+}
+
+#[test]
+fn corners() {
+    assert_eq!(
+        convert("25° Ramp Corner"),
+        ramp("PB_DefaultRampCorner", (15, 15, 6))
+    );
+    assert_eq!(
+        convert("45° Inv Ramp Corner"),
+        ramp("PB_DefaultRampInnerCorner", (10, 10, 6))
+    );
+    assert!(convert("45° Ramp 2x Corner").is_empty());
+}
+
+#[test]
+fn scaled_corners() {
+    assert_eq!(
+        convert("25° Ramp Corner 2x"),
+        ramp("PB_DefaultRampCorner", (30, 30, 12))
+    );
+    assert_eq!(
+        convert("-45° Ramp Corner 3x"),
+        ramp("PB_DefaultRampCornerInverted", (30, 30, 18))
+    );
+}
+
+#[test]
+fn footprint_ramps() {
+    // Without an angle they rise one brick
+    assert_eq!(convert("Ramp 1x6"), ramp("PB_DefaultRamp", (30, 5, 6)));
+    assert_eq!(convert("Ramp 1x8"), ramp("PB_DefaultRamp", (40, 5, 6)));
+    assert_eq!(convert("Ramp 2x6"), ramp("PB_DefaultRamp", (30, 10, 6)));
+    assert_eq!(convert("Ramp 2x8"), ramp("PB_DefaultRamp", (40, 10, 6)));
+}
+
+#[test]
+fn footprint_ramps_keep_their_angle() {
+    assert_eq!(convert("45° Ramp 1x4"), ramp("PB_DefaultRamp", (20, 5, 12)));
+    assert_eq!(
+        convert("25° Ramp 2x6"),
+        ramp("PB_DefaultRamp", (30, 10, 12))
+    );
+    assert_eq!(
+        convert("-45° Ramp 2x8"),
+        ramp("PB_DefaultRampInverted", (40, 10, 24))
+    );
+}
+
+#[test]
+fn rejected_ramps() {
+    assert!(convert("Ramp").is_empty());
+    assert!(convert("Ramp 4x").is_empty());
+    assert!(convert("Ramp 0x6").is_empty());
+    assert!(convert("Ramp 2x6 Corner").is_empty());
+    assert!(convert("25° Ramp Corner 0x").is_empty());
+}
@@ -0,0 +1,46 @@
+//! Converts single bricks with `Scale::Micro` and checks what they become.
+
+mod common;
+
+use bls2brs::{ConvertOptions, ConvertReport, FailureReason, Scale};
+use common::bricks;
+
+fn convert(ui_name: &str) -> ConvertReport {
+    let options = ConvertOptions {
+        scale: Scale::Micro,
+        ..ConvertOptions::default()
+    };
+    common::convert_brick(ui_name, &options)
+}
+
+#[test]
+fn boxes_scale_exactly() {
+    let report = convert("2x4");
+    assert_eq!(
+        bricks(&report),
+        vec![("PB_DefaultMicroBrick".to_string(), (2, 4, 1))]
+    );
+    assert!(report.approximate_ui_names.is_empty());
+}
+
+#[test]
+fn other_procedural_assets_become_boxes() {
+    let report = convert("45° Ramp");
+    assert_eq!(
+        bricks(&report),
+        vec![("PB_DefaultMicroBrick".to_string(), (2, 2, 1))]
+    );
+    assert_eq!(report.approximate_ui_names.get("45° Ramp"), Some(&1));
+}
+
+#[test]
+fn non_procedural_assets_fail() {
+    let report = convert("1x1 Cone");
+    assert!(report.write_data.bricks.is_empty());
+    assert_eq!(report.count_failure, 1);
+    assert_eq!(
+        report.failure_reasons.get(&FailureReason::NotScalable),
+        Some(&1)
+    );
+    assert!(report.unknown_ui_names.is_empty());
+}